@@ -1,26 +1,77 @@
-use crate::plugin::Plugin;
+use std::process::{ExitCode, Termination};
+use std::time::Duration;
+
+use crate::plugin::{self, Plugin};
+use crate::plugin_group::PluginGroup;
 use ecs::World;
-use event_manager::EventManager;
+use error::EngineResult;
+use event_manager::{EventCursor, EventManager, Recording};
 use logger::{debug, trace, Logger};
 use resource_manager::ResourceManager;
 use scheduler::Scheduler;
+use time::Time;
+
+/// Send it to stop the app at the end of the frame. Every run function honours it, cleans up the
+/// plugins and hands it back to `main` through [`App::run`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AppExit {
+    /// `None` for a normal exit.
+    pub code: Option<i32>,
+}
+
+impl AppExit {
+    pub fn with_code(code: i32) -> Self {
+        Self { code: Some(code) }
+    }
+
+    /// The code to exit the process with, 0 if there is none.
+    pub fn code(&self) -> i32 {
+        self.code.unwrap_or(0)
+    }
+}
+
+/// Lets `main` return what [`App::run`] does. Only the lowest byte of the code is kept, which is
+/// all most platforms keep anyway.
+impl Termination for AppExit {
+    fn report(self) -> ExitCode {
+        ExitCode::from(self.code() as u8)
+    }
+}
+
+/// Runs the app and returns the [`AppExit`] it stopped with.
+pub type RunFunction = Box<dyn FnOnce(App) -> AppExit>;
 
 pub struct App {
     world: World,
     resources: ResourceManager,
     events: EventManager,
     scheduler: Scheduler,
-    run_function: Box<dyn FnOnce(Self)>,
+    /// Set by the caller, wins over the one set by plugins.
+    run_function: Option<RunFunction>,
+    plugin_run_function: Option<RunFunction>,
+    initialising_plugins: bool,
+    exits: EventCursor<AppExit>,
+    /// Initialised plugins, in the order they were initialised in.
+    plugins: Vec<Box<dyn Plugin>>,
+    pending_plugins: Vec<Box<dyn Plugin>>,
 }
 
 impl Default for App {
     fn default() -> Self {
+        let mut resources = ResourceManager::default();
+        resources.add(Time::default());
+
         Self {
             world: World::default(),
-            resources: ResourceManager::default(),
+            resources,
             events: EventManager::default(),
             scheduler: Scheduler::default(),
-            run_function: Box::new(run_once),
+            run_function: None,
+            plugin_run_function: None,
+            initialising_plugins: false,
+            exits: EventCursor::new(),
+            plugins: Vec::new(),
+            pending_plugins: Vec::new(),
         }
     }
 }
@@ -31,27 +82,142 @@ impl App {
     }
 
     /// Takes by reference as it allows method chaining without taking ownership of a local variable.
-    pub fn run(&mut self) {
+    ///
+    /// Panics if the plugins can't be initialised, see [`App::init_plugins`], or the systems
+    /// can't be ordered, see [`Scheduler::build`], before the run function gets to run anything.
+    pub fn run(&mut self) -> AppExit {
+        if let Err(err) = self.init_plugins() {
+            panic!("Failed to initialise plugins: {err}");
+        }
+        if let Err(err) = self.scheduler.build() {
+            panic!("Failed to build the schedule: {err}");
+        }
+
         let mut app = std::mem::take(self);
-        let run_function = std::mem::replace(&mut app.run_function, Box::new(run_once));
-        run_function(app);
+        let run_function = app
+            .run_function
+            .take()
+            .or_else(|| app.plugin_run_function.take())
+            .unwrap_or_else(|| Box::new(run_once));
+        run_function(app)
     }
 
     pub fn update(&mut self) {
-        self.scheduler.update(&mut self.events, &mut self.resources);
+        self.update_with_delta(None);
+    }
+
+    /// Like [`App::update`], but advances [`Time`] by `delta` instead of reading the wall clock
+    /// if there is one.
+    fn update_with_delta(&mut self, delta: Option<Duration>) {
+        if let Some(time) = self.resources.get_mut::<Time>() {
+            match delta {
+                Some(delta) => time.advance_by(delta),
+                None => time.update(),
+            }
+            self.events.record_frame_delta(time.real_delta());
+        }
+        self.events.update();
+
+        self.scheduler
+            .update(&mut self.world, &mut self.events, &mut self.resources);
+    }
+
+    /// The last [`AppExit`] sent since the previous call, for run functions to check after every
+    /// update.
+    pub fn exit_requested(&mut self) -> Option<AppExit> {
+        self.exits.read(&self.events).last().copied()
     }
 
-    pub fn set_run_function(&mut self, run_function: Box<dyn FnOnce(Self)>) -> &mut Self {
-        debug!("Replaced the run function.");
-        self.run_function = run_function;
+    /// A run function set by a plugin while it's initialised is only used if the caller doesn't
+    /// set one, whether before or after adding the plugin.
+    pub fn set_run_function(&mut self, run_function: RunFunction) -> &mut Self {
+        if self.initialising_plugins {
+            debug!("A plugin replaced the default run function.");
+            self.plugin_run_function = Some(run_function);
+        } else {
+            debug!("Replaced the run function.");
+            self.run_function = Some(run_function);
+        }
         self
     }
 
+    /// Replaces the run function with one that feeds `recording` to the event manager frame by
+    /// frame instead of taking live input, and stops after the last recorded frame. Time advances
+    /// by the recorded frame deltas rather than following the wall clock.
+    pub fn replay(&mut self, recording: Recording) -> &mut Self {
+        self.set_run_function(Box::new(move |app| run_replay(app, recording)))
+    }
+
+    /// The plugin is initialised later, by [`App::init_plugins`].
     pub fn add_plugin<T: Plugin>(&mut self, plugin: T) -> &mut Self {
-        plugin.init(self);
+        self.add_boxed_plugin(Box::new(plugin))
+    }
+
+    /// Adds the group's enabled plugins, in the group's order.
+    pub fn add_plugins<G: PluginGroup>(&mut self, group: G) -> &mut Self {
+        group.build().finish(self);
         self
     }
 
+    pub(crate) fn add_boxed_plugin(&mut self, plugin: Box<dyn Plugin>) -> &mut Self {
+        self.pending_plugins.push(plugin);
+        self
+    }
+
+    /// Initialises the plugins added since the last call, each after the plugins it depends on
+    /// and otherwise in the order they were added, then finishes them in the same order. Plugins
+    /// added while initialising are initialised before anything is finished.
+    ///
+    /// Fails if a unique plugin was added twice, a plugin depends on a plugin that wasn't added,
+    /// or the dependencies form a cycle. Plugins added before the call are checked before any of
+    /// them is initialised, but plugins added while initialising are only checked once those
+    /// have been, so a mistake in them leaves the earlier plugins initialised and unfinished.
+    pub fn init_plugins(&mut self) -> EngineResult<()> {
+        self.initialising_plugins = true;
+        let result = self.init_pending_plugins();
+        self.initialising_plugins = false;
+        result
+    }
+
+    fn init_pending_plugins(&mut self) -> EngineResult<()> {
+        while !self.pending_plugins.is_empty() {
+            let first = self.plugins.len();
+            while !self.pending_plugins.is_empty() {
+                let pending = std::mem::take(&mut self.pending_plugins);
+                for plugin in plugin::sort(pending, &self.plugins)? {
+                    debug!("Initialising plugin {}", plugin.name());
+                    plugin.init(self);
+                    self.plugins.push(plugin);
+                }
+            }
+
+            let plugins = std::mem::take(&mut self.plugins);
+            for plugin in &plugins[first..] {
+                plugin.finish(self);
+            }
+            self.plugins = plugins;
+        }
+        Ok(())
+    }
+
+    /// Cleans up the plugins in the reverse of the order they were initialised in and drops
+    /// them. Run functions call it once they stop.
+    pub fn cleanup(&mut self) {
+        let plugins = std::mem::take(&mut self.plugins);
+        for plugin in plugins.iter().rev() {
+            debug!("Cleaning up plugin {}", plugin.name());
+            plugin.cleanup(self);
+        }
+    }
+
+    pub fn get_world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn get_world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
     pub fn get_resource_manager(&self) -> &ResourceManager {
         &self.resources
     }
@@ -85,6 +251,34 @@ impl App {
     }
 }
 
-fn run_once(_app: App) {
-    trace!("Default run function.")
+fn run_once(mut app: App) -> AppExit {
+    trace!("Default run function.");
+    app.cleanup();
+    AppExit::default()
+}
+
+fn run_replay(mut app: App, recording: Recording) -> AppExit {
+    let Some(last_frame) = recording.last_frame() else {
+        debug!("Replaying an empty recording.");
+        app.cleanup();
+        return AppExit::default();
+    };
+
+    // Events recorded in a frame were added after that frame's update, so they go in before
+    // the next one.
+    let mut exit = AppExit::default();
+    while app.events.frame() <= last_frame {
+        let frame = app.events.frame();
+        if let Err(err) = recording.play_frame(frame, &mut app.events) {
+            panic!("Failed to replay frame {frame}: {err}");
+        }
+        app.update_with_delta(recording.frame_delta(frame));
+
+        if let Some(requested) = app.exit_requested() {
+            exit = requested;
+            break;
+        }
+    }
+    app.cleanup();
+    exit
 }
@@ -1,5 +1,8 @@
 pub mod app;
 pub mod plugin;
+pub mod plugin_group;
+pub mod runner;
 
-pub use app::App;
+pub use app::{App, AppExit, RunFunction};
 pub use plugin::Plugin;
+pub use plugin_group::{PluginGroup, PluginGroupBuilder};
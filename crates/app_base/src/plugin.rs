@@ -1,5 +1,92 @@
+use error::{engine_error, EngineResult};
+
 use crate::app::App;
 
-pub trait Plugin {
+/// Plugins go through three steps: every plugin is initialised, then every plugin is finished,
+/// both in dependency order, and once the app stops they are cleaned up in reverse order.
+pub trait Plugin: 'static {
+    /// Builds the plugin, adding its systems and resources to `app`.
     fn init(&self, app: &mut App);
+
+    /// Called once every plugin has been initialised, for wiring that needs other plugins'
+    /// resources.
+    fn finish(&self, _app: &mut App) {}
+
+    /// Called when the app stops, before the plugins it depends on are cleaned up.
+    fn cleanup(&self, _app: &mut App) {}
+
+    /// Whether adding the plugin more than once is an error.
+    fn is_unique(&self) -> bool {
+        true
+    }
+
+    /// Identifies the plugin in dependencies and errors. The type name unless overridden.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Names of the plugins that have to be initialised before this one.
+    fn dependencies(&self) -> Vec<&str> {
+        Vec::new()
+    }
+}
+
+/// Orders `pending` so every plugin comes after its dependencies, keeping the order they were
+/// added in where there are none. Dependencies on `initialised` plugins are already met.
+pub(crate) fn sort(
+    pending: Vec<Box<dyn Plugin>>,
+    initialised: &[Box<dyn Plugin>],
+) -> EngineResult<Vec<Box<dyn Plugin>>> {
+    let mut successors = vec![Vec::new(); pending.len()];
+
+    for (index, plugin) in pending.iter().enumerate() {
+        let name = plugin.name();
+        if plugin.is_unique()
+            && initialised
+                .iter()
+                .chain(&pending[..index])
+                .any(|other| other.name() == name)
+        {
+            return engine_error!("DuplicatePlugin", "Plugin {name} was added more than once")
+                .as_result();
+        }
+
+        for dependency in plugin.dependencies() {
+            if initialised.iter().any(|other| other.name() == dependency) {
+                continue;
+            }
+            match pending.iter().position(|other| other.name() == dependency) {
+                Some(other) => successors[other].push(index),
+                None => {
+                    return engine_error!(
+                        "MissingPlugin",
+                        "Plugin {name} depends on {dependency}, which was never added"
+                    )
+                    .as_result()
+                }
+            }
+        }
+    }
+
+    let order = match utils::topological_sort(&successors) {
+        Ok(order) => order,
+        Err(cycle) => {
+            let cycle: Vec<_> = cycle
+                .into_iter()
+                .map(|index| pending[index].name())
+                .collect();
+            return engine_error!(
+                "PluginDependencyCycle",
+                "Plugin dependencies form a cycle between: {}",
+                cycle.join(", ")
+            )
+            .as_result();
+        }
+    };
+
+    let mut pending: Vec<_> = pending.into_iter().map(Some).collect();
+    Ok(order
+        .into_iter()
+        .map(|index| pending[index].take().unwrap())
+        .collect())
 }
@@ -0,0 +1,135 @@
+use std::any::TypeId;
+
+use crate::app::App;
+use crate::plugin::Plugin;
+
+/// A set of plugins that are added together, like the engine's default plugins. Turn it into a
+/// [`PluginGroupBuilder`] with [`PluginGroup::build`] to change its members before adding it.
+pub trait PluginGroup: Sized {
+    fn build(self) -> PluginGroupBuilder;
+
+    fn name() -> &'static str {
+        std::any::type_name::<Self>()
+    }
+}
+
+struct Entry {
+    id: TypeId,
+    plugin: Box<dyn Plugin>,
+    enabled: bool,
+}
+
+/// The members of a [`PluginGroup`], in the order they are added to the app. Members are picked
+/// out by their type, and the methods naming one panic if the group doesn't have it.
+pub struct PluginGroupBuilder {
+    group: &'static str,
+    plugins: Vec<Entry>,
+}
+
+impl PluginGroupBuilder {
+    pub fn start<G: PluginGroup>() -> Self {
+        Self {
+            group: G::name(),
+            plugins: Vec::new(),
+        }
+    }
+
+    /// Adds the plugin at the end, or replaces the one of the same type where it is.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add<T: Plugin>(mut self, plugin: T) -> Self {
+        match self.position::<T>() {
+            Some(index) => self.plugins[index] = Entry::new(plugin),
+            None => self.plugins.push(Entry::new(plugin)),
+        }
+        self
+    }
+
+    /// Adds the plugin right before `Target`, moving it if the group already has one. Before
+    /// itself, it takes the place of the one there.
+    pub fn add_before<Target: Plugin, T: Plugin>(self, plugin: T) -> Self {
+        self.insert::<Target, T>(plugin, 0)
+    }
+
+    /// Adds the plugin right after `Target`, moving it if the group already has one. After
+    /// itself, it takes the place of the one there.
+    pub fn add_after<Target: Plugin, T: Plugin>(self, plugin: T) -> Self {
+        self.insert::<Target, T>(plugin, 1)
+    }
+
+    /// Puts `plugin` in the place of the member `Old`, like a headless renderer instead of the
+    /// default one. A `New` that was already a member elsewhere is removed.
+    pub fn replace<Old: Plugin, New: Plugin>(mut self, plugin: New) -> Self {
+        if TypeId::of::<Old>() != TypeId::of::<New>() {
+            if let Some(index) = self.position::<New>() {
+                self.plugins.remove(index);
+            }
+        }
+        let index = self.index::<Old>();
+        self.plugins[index] = Entry::new(plugin);
+        self
+    }
+
+    /// Keeps the member `T` from being added, without changing the order of the others.
+    pub fn disable<T: Plugin>(mut self) -> Self {
+        let index = self.index::<T>();
+        self.plugins[index].enabled = false;
+        self
+    }
+
+    pub fn enable<T: Plugin>(mut self) -> Self {
+        let index = self.index::<T>();
+        self.plugins[index].enabled = true;
+        self
+    }
+
+    /// Adds the enabled members to `app`, in order.
+    pub fn finish(self, app: &mut App) {
+        for entry in self.plugins.into_iter().filter(|entry| entry.enabled) {
+            app.add_boxed_plugin(entry.plugin);
+        }
+    }
+
+    fn insert<Target: Plugin, T: Plugin>(mut self, plugin: T, offset: usize) -> Self {
+        if TypeId::of::<Target>() == TypeId::of::<T>() {
+            return self.replace::<Target, T>(plugin);
+        }
+        if let Some(index) = self.position::<T>() {
+            self.plugins.remove(index);
+        }
+        let index = self.index::<Target>() + offset;
+        self.plugins.insert(index, Entry::new(plugin));
+        self
+    }
+
+    fn position<T: Plugin>(&self) -> Option<usize> {
+        let id = TypeId::of::<T>();
+        self.plugins.iter().position(|entry| entry.id == id)
+    }
+
+    fn index<T: Plugin>(&self) -> usize {
+        match self.position::<T>() {
+            Some(index) => index,
+            None => panic!(
+                "Plugin group {} has no plugin {}",
+                self.group,
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+}
+
+impl PluginGroup for PluginGroupBuilder {
+    fn build(self) -> PluginGroupBuilder {
+        self
+    }
+}
+
+impl Entry {
+    fn new<T: Plugin>(plugin: T) -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            plugin: Box::new(plugin),
+            enabled: true,
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Run functions for apps without a window, to pass to [`App::set_run_function`].
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use logger::debug;
+
+use crate::app::{App, AppExit, RunFunction};
+
+/// Updates the app at most once every `wait`, sleeping off whatever a frame leaves of it, until
+/// an [`AppExit`] is sent.
+pub fn headless(wait: Duration) -> RunFunction {
+    Box::new(move |app| run_loop(app, None, wait))
+}
+
+/// Updates the app `count` times as fast as it can, stopping early if an [`AppExit`] is sent.
+pub fn frames(count: u64) -> RunFunction {
+    Box::new(move |app| run_loop(app, Some(count), Duration::ZERO))
+}
+
+/// Updates the app as fast as it can until an [`AppExit`] is sent.
+pub fn until_exit() -> RunFunction {
+    Box::new(|app| run_loop(app, None, Duration::ZERO))
+}
+
+fn run_loop(mut app: App, frames: Option<u64>, wait: Duration) -> AppExit {
+    let mut exit = AppExit::default();
+    let mut frame = 0;
+    while frames.is_none_or(|frames| frame < frames) {
+        let start = Instant::now();
+        app.update();
+        frame += 1;
+
+        if let Some(requested) = app.exit_requested() {
+            debug!("Exiting after {frame} frames.");
+            exit = requested;
+            break;
+        }
+        if let Some(left) = wait.checked_sub(start.elapsed()) {
+            thread::sleep(left);
+        }
+    }
+    app.cleanup();
+    exit
+}
@@ -0,0 +1,146 @@
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use app_base::{App, Plugin};
+
+    type Log = Arc<Mutex<Vec<String>>>;
+
+    struct Named {
+        name: &'static str,
+        dependencies: Vec<&'static str>,
+        log: Log,
+    }
+
+    impl Named {
+        fn new(name: &'static str, dependencies: &[&'static str], log: &Log) -> Self {
+            Self {
+                name,
+                dependencies: dependencies.to_vec(),
+                log: log.clone(),
+            }
+        }
+
+        fn log(&self, step: &str) {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{step} {}", self.name));
+        }
+    }
+
+    impl Plugin for Named {
+        fn init(&self, app: &mut App) {
+            self.log("init");
+            if self.name == "ui" {
+                app.add_plugin(Named::new("fonts", &[], &self.log));
+            }
+        }
+
+        fn finish(&self, _app: &mut App) {
+            self.log("finish");
+        }
+
+        fn cleanup(&self, _app: &mut App) {
+            self.log("cleanup");
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn dependencies(&self) -> Vec<&str> {
+            self.dependencies.clone()
+        }
+
+        fn is_unique(&self) -> bool {
+            self.name != "shared"
+        }
+    }
+
+    fn take(log: &Log) -> Vec<String> {
+        std::mem::take(&mut *log.lock().unwrap())
+    }
+
+    #[test]
+    fn plugins_are_initialised_after_their_dependencies() {
+        let log = Log::default();
+        let mut app = App::new();
+        app.add_plugin(Named::new("renderer", &["window"], &log))
+            .add_plugin(Named::new("audio", &[], &log))
+            .add_plugin(Named::new("window", &[], &log));
+        assert!(log.lock().unwrap().is_empty());
+
+        app.init_plugins().unwrap();
+        assert_eq!(
+            take(&log),
+            [
+                "init audio",
+                "init window",
+                "init renderer",
+                "finish audio",
+                "finish window",
+                "finish renderer",
+            ]
+        );
+
+        app.add_plugin(Named::new("ui", &["renderer"], &log));
+        app.init_plugins().unwrap();
+        assert_eq!(
+            take(&log),
+            ["init ui", "init fonts", "finish ui", "finish fonts"]
+        );
+    }
+
+    #[test]
+    fn plugins_are_cleaned_up_in_reverse() {
+        let log = Log::default();
+        let mut app = App::new();
+        app.add_plugin(Named::new("renderer", &["window"], &log))
+            .add_plugin(Named::new("window", &[], &log))
+            .add_plugin(Named::new("shared", &[], &log))
+            .add_plugin(Named::new("shared", &[], &log));
+        app.run();
+
+        let log = take(&log);
+        assert_eq!(
+            log[log.len() - 4..],
+            [
+                "cleanup shared",
+                "cleanup shared",
+                "cleanup renderer",
+                "cleanup window"
+            ]
+        );
+    }
+
+    #[test]
+    fn plugin_mistakes_are_errors() {
+        let log = Log::default();
+        let error = |plugins: Vec<Named>| {
+            let mut app = App::new();
+            for plugin in plugins {
+                app.add_plugin(plugin);
+            }
+            app.init_plugins().unwrap_err()
+        };
+
+        let err = error(vec![Named::new("renderer", &["window"], &log)]);
+        assert_eq!(err.get_id(), "MissingPlugin");
+        assert!(err.get_msg().contains("renderer depends on window"));
+
+        let err = error(vec![
+            Named::new("window", &[], &log),
+            Named::new("window", &[], &log),
+        ]);
+        assert_eq!(err.get_id(), "DuplicatePlugin");
+
+        let err = error(vec![
+            Named::new("a", &["b"], &log),
+            Named::new("b", &["a"], &log),
+        ]);
+        assert_eq!(err.get_id(), "PluginDependencyCycle");
+
+        assert!(log.lock().unwrap().is_empty());
+    }
+}
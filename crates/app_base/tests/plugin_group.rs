@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod test {
+    use app_base::{App, Plugin, PluginGroup, PluginGroupBuilder};
+
+    #[derive(Default)]
+    struct Order(Vec<&'static str>);
+
+    macro_rules! plugins {
+        ($($name:ident),*) => {
+            $(
+                struct $name;
+
+                impl Plugin for $name {
+                    fn init(&self, app: &mut App) {
+                        app.get_resource_manager_mut()
+                            .get_mut_unchecked::<Order>()
+                            .0
+                            .push(stringify!($name));
+                    }
+                }
+            )*
+        };
+    }
+
+    plugins!(Window, Renderer, Audio, Headless, Ui);
+
+    struct Defaults;
+
+    impl PluginGroup for Defaults {
+        fn build(self) -> PluginGroupBuilder {
+            PluginGroupBuilder::start::<Self>()
+                .add(Window)
+                .add(Renderer)
+                .add(Audio)
+        }
+    }
+
+    fn added(group: impl PluginGroup) -> Vec<&'static str> {
+        let mut app = App::new();
+        app.get_resource_manager_mut().add(Order::default());
+        app.add_plugins(group).init_plugins().unwrap();
+        let order = app.get_resource_manager().get_unchecked::<Order>();
+        order.0.clone()
+    }
+
+    #[test]
+    fn groups_add_their_plugins_in_order() {
+        assert_eq!(added(Defaults), ["Window", "Renderer", "Audio"]);
+    }
+
+    #[test]
+    fn members_can_be_changed_before_adding_the_group() {
+        let group = Defaults
+            .build()
+            .replace::<Renderer, _>(Headless)
+            .disable::<Audio>()
+            .add_before::<Window, _>(Ui);
+        assert_eq!(added(group), ["Ui", "Window", "Headless"]);
+
+        let group = Defaults
+            .build()
+            .add_after::<Audio, _>(Window)
+            .disable::<Renderer>()
+            .enable::<Renderer>();
+        assert_eq!(added(group), ["Renderer", "Audio", "Window"]);
+    }
+
+    #[test]
+    fn members_are_never_added_twice() {
+        let group = Defaults.build().replace::<Window, _>(Audio);
+        assert_eq!(added(group), ["Audio", "Renderer"]);
+
+        let group = Defaults
+            .build()
+            .add_before::<Renderer, _>(Renderer)
+            .add_after::<Audio, _>(Audio);
+        assert_eq!(added(group), ["Window", "Renderer", "Audio"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no plugin")]
+    fn changing_a_missing_member_panics() {
+        Defaults.build().disable::<Ui>();
+    }
+}
@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod test {
+    use std::io::{self, Write};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use app_base::{runner, App};
+    use event_manager::{EventReader, RecordableEvent, Recording};
+    use scheduler::{FixedTime, Res};
+
+    struct Clicked(u32);
+
+    impl RecordableEvent for Clicked {
+        const NAME: &'static str = "Clicked";
+
+        fn encode(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn decode(data: &str) -> Option<Self> {
+            data.parse().ok().map(Self)
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// An app taking fixed steps at 1000 Hz, which stores how many it took in `steps`.
+    fn stepping_app(steps: &Arc<AtomicU64>) -> App {
+        let mut app = App::new();
+        app.get_resource_manager_mut()
+            .add(FixedTime::from_hz(1000.0));
+        app.get_scheduler_mut().add_system({
+            let steps = steps.clone();
+            move |fixed: Res<FixedTime>| steps.store(fixed.steps(), Ordering::Relaxed)
+        });
+        app
+    }
+
+    #[test]
+    fn replay_feeds_recorded_events_to_systems() {
+        let data = "spyder-events 1\n0 Clicked 1\n2 Clicked 2\n2 Clicked 3\n";
+        let mut recording = Recording::read(data.as_bytes()).unwrap();
+        recording.register::<Clicked>();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let mut app = App::new();
+        app.get_scheduler_mut().add_system({
+            let seen = seen.clone();
+            move |mut clicks: EventReader<Clicked>| {
+                let clicks: Vec<_> = clicks.read().map(|click| click.0).collect();
+                seen.lock().unwrap().push(clicks);
+            }
+        });
+        app.replay(recording).run();
+
+        let expected: [Vec<u32>; 3] = [vec![1], vec![], vec![2, 3]];
+        assert_eq!(*seen.lock().unwrap(), expected);
+    }
+
+    #[test]
+    fn replay_advances_time_by_the_recorded_deltas() {
+        let buffer = Buffer::default();
+        let recorded_steps = Arc::new(AtomicU64::new(0));
+        let mut app = stepping_app(&recorded_steps);
+        app.get_event_manager_mut()
+            .start_recording(buffer.clone())
+            .unwrap();
+        app.get_scheduler_mut()
+            .add_system(|| std::thread::sleep(Duration::from_millis(3)));
+        app.set_run_function(runner::frames(5)).run();
+
+        let data = buffer.0.lock().unwrap().clone();
+        let recording = Recording::read(data.as_slice()).unwrap();
+        assert_eq!(recording.last_frame(), Some(4));
+
+        // Replays without sleeping, so following the wall clock would take far fewer steps.
+        let replayed_steps = Arc::new(AtomicU64::new(0));
+        stepping_app(&replayed_steps).replay(recording).run();
+
+        assert!(recorded_steps.load(Ordering::Relaxed) >= 12);
+        assert_eq!(
+            replayed_steps.load(Ordering::Relaxed),
+            recorded_steps.load(Ordering::Relaxed)
+        );
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    use app_base::{runner, App, AppExit, Plugin};
+    use scheduler::{EventWriter, ResMut};
+
+    /// Sets a runner the way a windowing plugin would.
+    struct RunnerPlugin;
+
+    impl Plugin for RunnerPlugin {
+        fn init(&self, app: &mut App) {
+            app.set_run_function(runner::frames(2));
+        }
+    }
+
+    /// An app counting its frames, which exits with code 3 on frame `exit_on`.
+    fn counting_app(exit_on: u32) -> (App, Arc<AtomicU32>) {
+        let frames = Arc::new(AtomicU32::new(0));
+        let mut app = App::new();
+        app.get_scheduler_mut().add_system({
+            let frames = frames.clone();
+            move |mut exit: EventWriter<AppExit>| {
+                if frames.fetch_add(1, Ordering::Relaxed) + 1 == exit_on {
+                    exit.send(AppExit::with_code(3));
+                }
+            }
+        });
+        (app, frames)
+    }
+
+    #[test]
+    fn running_a_number_of_frames() {
+        let (mut app, frames) = counting_app(0);
+        let exit = app.set_run_function(runner::frames(5)).run();
+        assert_eq!(frames.load(Ordering::Relaxed), 5);
+        assert_eq!(exit, AppExit::default());
+
+        let (mut app, frames) = counting_app(2);
+        let exit = app.set_run_function(runner::frames(5)).run();
+        assert_eq!(frames.load(Ordering::Relaxed), 2);
+        assert_eq!(exit.code(), 3);
+    }
+
+    #[test]
+    fn running_until_exit() {
+        let (mut app, frames) = counting_app(4);
+        let exit = app.set_run_function(runner::until_exit()).run();
+        assert_eq!(frames.load(Ordering::Relaxed), 4);
+        assert_eq!(exit, AppExit::with_code(3));
+    }
+
+    #[test]
+    fn headless_runs_wait_between_frames() {
+        let (mut app, frames) = counting_app(3);
+        let start = Instant::now();
+        app.set_run_function(runner::headless(Duration::from_millis(10)))
+            .run();
+        assert_eq!(frames.load(Ordering::Relaxed), 3);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn plugins_only_set_the_default_run_function() {
+        let (mut app, frames) = counting_app(0);
+        app.add_plugin(RunnerPlugin).run();
+        assert_eq!(frames.load(Ordering::Relaxed), 2);
+
+        let (mut app, frames) = counting_app(0);
+        app.set_run_function(runner::frames(5))
+            .add_plugin(RunnerPlugin)
+            .run();
+        assert_eq!(frames.load(Ordering::Relaxed), 5);
+
+        let (mut app, frames) = counting_app(0);
+        app.add_plugin(RunnerPlugin)
+            .set_run_function(runner::frames(4))
+            .run();
+        assert_eq!(frames.load(Ordering::Relaxed), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to build the schedule")]
+    fn ambiguous_systems_fail_before_running() {
+        fn score(_: ResMut<u32>) {}
+
+        let mut app = App::new();
+        app.get_scheduler_mut().add_system(score);
+        app.get_scheduler_mut().add_system(score);
+        app.set_run_function(runner::frames(1)).run();
+    }
+}
@@ -0,0 +1,137 @@
+//! Compares iterating 100k entities in the sparse set storage against the old layout of one
+//! boxed component per `(Entity, TypeId)` key. Run with `cargo bench -p ecs`.
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    hint::black_box,
+    time::Instant,
+};
+
+use ecs::{Entity, World};
+
+const ENTITY_COUNT: usize = 100_000;
+const ITERATIONS: u32 = 100;
+
+struct Position(f32);
+struct Velocity(f32);
+
+#[derive(Default)]
+struct NaiveWorld {
+    entities: Vec<Entity>,
+    components: HashMap<(Entity, TypeId), Box<dyn Any>>,
+}
+
+impl NaiveWorld {
+    fn insert<C: Any>(&mut self, entity: Entity, component: C) {
+        self.components
+            .insert((entity, TypeId::of::<C>()), Box::new(component));
+    }
+
+    fn get<C: Any>(&self, entity: Entity) -> Option<&C> {
+        self.components
+            .get(&(entity, TypeId::of::<C>()))
+            .map(|component| component.downcast_ref().unwrap())
+    }
+
+    fn get_mut<C: Any>(&mut self, entity: Entity) -> Option<&mut C> {
+        self.components
+            .get_mut(&(entity, TypeId::of::<C>()))
+            .map(|component| component.downcast_mut().unwrap())
+    }
+}
+
+fn setup() -> (World, NaiveWorld) {
+    let mut world = World::new();
+    let mut naive = NaiveWorld::default();
+
+    for i in 0..ENTITY_COUNT {
+        let entity = world.spawn();
+        world.insert(entity, Position(i as f32));
+        world.insert(entity, Velocity(1.0));
+
+        naive.entities.push(entity);
+        naive.insert(entity, Position(i as f32));
+        naive.insert(entity, Velocity(1.0));
+    }
+
+    (world, naive)
+}
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    f();
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let per_iteration = start.elapsed() / ITERATIONS;
+
+    println!(
+        "{name:<32} {:>10.3} ms/iter  {:>8.2} ns/entity",
+        per_iteration.as_secs_f64() * 1000.0,
+        per_iteration.as_nanos() as f64 / ENTITY_COUNT as f64
+    );
+}
+
+fn main() {
+    let (mut world, mut naive) = setup();
+    println!("Iterating {ENTITY_COUNT} entities, {ITERATIONS} iterations each");
+
+    bench("sparse set: read one", || {
+        let sum: f32 = world.iter::<Position>().map(|(_, p)| p.0).sum();
+        black_box(sum);
+    });
+
+    bench("naive: read one", || {
+        let sum: f32 = naive
+            .entities
+            .iter()
+            .filter_map(|entity| naive.get::<Position>(*entity))
+            .map(|p| p.0)
+            .sum();
+        black_box(sum);
+    });
+
+    bench("sparse set: write one", || {
+        for (_, position) in world.iter_mut::<Position>() {
+            position.0 += 1.0;
+        }
+    });
+
+    bench("naive: write one", || {
+        for i in 0..naive.entities.len() {
+            let entity = naive.entities[i];
+            if let Some(position) = naive.get_mut::<Position>(entity) {
+                position.0 += 1.0;
+            }
+        }
+    });
+
+    bench("sparse set: read two", || {
+        let sum: f32 = world
+            .iter::<Velocity>()
+            .filter_map(|(entity, v)| world.get::<Position>(entity).map(|p| p.0 * v.0))
+            .sum();
+        black_box(sum);
+    });
+
+    bench("sparse set: query two", || {
+        let query = world.query::<(&Position, &Velocity)>();
+        let sum: f32 = query.iter().map(|(p, v)| p.0 * v.0).sum();
+        black_box(sum);
+    });
+
+    bench("naive: read two", || {
+        let sum: f32 = naive
+            .entities
+            .iter()
+            .filter_map(|entity| {
+                let velocity = naive.get::<Velocity>(*entity)?;
+                let position = naive.get::<Position>(*entity)?;
+                Some(position.0 * velocity.0)
+            })
+            .sum();
+        black_box(sum);
+    });
+}
@@ -0,0 +1,89 @@
+use std::any::TypeId;
+
+use crate::component::Component;
+
+#[derive(Clone, Copy, Debug)]
+struct ComponentId {
+    id: TypeId,
+    name: &'static str,
+}
+
+impl ComponentId {
+    fn of<C: Component>() -> Self {
+        Self {
+            id: TypeId::of::<C>(),
+            name: std::any::type_name::<C>(),
+        }
+    }
+}
+
+/// The set of component types something reads and writes.
+#[derive(Clone, Debug, Default)]
+pub struct ComponentAccess {
+    reads: Vec<ComponentId>,
+    writes: Vec<ComponentId>,
+}
+
+impl ComponentAccess {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_read<C: Component>(&mut self) {
+        self.reads.push(ComponentId::of::<C>());
+    }
+
+    pub fn add_write<C: Component>(&mut self) {
+        self.writes.push(ComponentId::of::<C>());
+    }
+
+    pub fn extend(&mut self, other: &ComponentAccess) {
+        self.reads.extend_from_slice(&other.reads);
+        self.writes.extend_from_slice(&other.writes);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reads.is_empty() && self.writes.is_empty()
+    }
+
+    pub fn reads(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.reads.iter().map(|component| component.id)
+    }
+
+    pub fn writes(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.writes.iter().map(|component| component.id)
+    }
+
+    /// Names of the components that are written more than once, or both written and read.
+    pub fn self_conflicts(&self) -> Vec<&'static str> {
+        let mut conflicts = Vec::new();
+        for (i, write) in self.writes.iter().enumerate() {
+            let aliased = self.writes[i + 1..]
+                .iter()
+                .chain(&self.reads)
+                .any(|other| other.id == write.id);
+            if aliased && !conflicts.contains(&write.name) {
+                conflicts.push(write.name);
+            }
+        }
+        conflicts
+    }
+
+    /// Names of the components that can't be accessed by both at the same time.
+    pub fn conflicts_with(&self, other: &ComponentAccess) -> Vec<&'static str> {
+        let mut conflicts = Vec::new();
+        let mut check = |writes: &[ComponentId], accessed: &[ComponentId]| {
+            for write in writes {
+                if accessed.iter().any(|other| other.id == write.id)
+                    && !conflicts.contains(&write.name)
+                {
+                    conflicts.push(write.name);
+                }
+            }
+        };
+        check(&self.writes, &other.writes);
+        check(&self.writes, &other.reads);
+        check(&other.writes, &self.reads);
+        conflicts
+    }
+}
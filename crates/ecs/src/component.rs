@@ -0,0 +1,7 @@
+/// Anything that can be stored on an entity.
+///
+/// Components have to be shareable between threads so systems touching disjoint data can run
+/// in parallel.
+pub trait Component: Send + Sync + 'static {}
+
+impl<T: Send + Sync + 'static> Component for T {}
@@ -1,5 +1,81 @@
-/// For now the entity is a simple UUID
-#[derive(Clone, Copy)]
+/// Handle to an entity in a [`World`](crate::World).
+///
+/// The generation is bumped every time a slot is reused, so a handle kept around after its
+/// entity was despawned will not alias whatever gets spawned into the same slot later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Entity {
-    uuid: u64,
+    index: u32,
+    generation: u32,
+}
+
+impl Entity {
+    pub(crate) fn new(index: u32, generation: u32) -> Self {
+        Self { index, generation }
+    }
+
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct EntityAllocator {
+    generations: Vec<u32>,
+    alive: Vec<bool>,
+    free: Vec<u32>,
+    len: usize,
+}
+
+impl EntityAllocator {
+    pub fn alloc(&mut self) -> Entity {
+        self.len += 1;
+        match self.free.pop() {
+            Some(index) => {
+                self.alive[index as usize] = true;
+                Entity::new(index, self.generations[index as usize])
+            }
+            None => {
+                let index = self.generations.len() as u32;
+                self.generations.push(0);
+                self.alive.push(true);
+                Entity::new(index, 0)
+            }
+        }
+    }
+
+    pub fn free(&mut self, entity: Entity) -> bool {
+        if !self.is_alive(entity) {
+            return false;
+        }
+
+        let index = entity.index as usize;
+        self.alive[index] = false;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+        self.free.push(entity.index);
+        self.len -= 1;
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        let index = entity.index as usize;
+        index < self.alive.len()
+            && self.alive[index]
+            && self.generations[index] == entity.generation
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.alive
+            .iter()
+            .enumerate()
+            .filter(|(_, alive)| **alive)
+            .map(|(index, _)| Entity::new(index as u32, self.generations[index]))
+    }
 }
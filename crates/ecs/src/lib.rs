@@ -1,3 +1,12 @@
+pub mod access;
+pub mod component;
 pub mod entity;
+pub mod query;
+pub mod storage;
 pub mod world;
+
+pub use crate::access::ComponentAccess;
+pub use crate::component::Component;
+pub use crate::entity::Entity;
+pub use crate::query::{Query, With, Without};
 pub use crate::world::World;
@@ -0,0 +1,378 @@
+use std::marker::PhantomData;
+
+use crate::access::ComponentAccess;
+use crate::component::Component;
+use crate::entity::Entity;
+use crate::storage::{SparseIndex, SparseSet};
+use crate::world::World;
+
+/// Something that can be fetched per entity by a [`Query`], e.g. `&A`, `&mut B`, `Option<&C>`,
+/// `Entity` or a tuple of those.
+///
+/// # Safety
+/// `access` has to report every component that `fetch` reads or writes.
+pub unsafe trait QueryData {
+    type Item<'a>;
+    type Fetch<'w>;
+
+    fn access(access: &mut ComponentAccess);
+
+    /// # Safety
+    /// The access reported by [`QueryData::access`] must be valid for the lifetime of the fetch.
+    unsafe fn init_fetch(world: &World) -> Self::Fetch<'_>;
+
+    /// The entities that can possibly match, if this narrows them down at all.
+    fn candidates<'w>(fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]>;
+
+    /// # Safety
+    /// Mutable items for the same entity must not be alive at the same time.
+    unsafe fn fetch<'a>(fetch: &'a Self::Fetch<'_>, entity: Entity) -> Option<Self::Item<'a>>;
+}
+
+/// Marks query data that never hands out mutable references.
+///
+/// # Safety
+/// Only implement this when [`QueryData::access`] reports no writes.
+pub unsafe trait ReadOnlyQueryData: QueryData {}
+
+/// Narrows down which entities a [`Query`] matches without fetching anything.
+///
+/// # Safety
+/// `access` has to report every component storage that `init_fetch` reads, even if only to look
+/// at which entities have the component.
+pub unsafe trait QueryFilter {
+    type Fetch<'w>;
+
+    fn access(access: &mut ComponentAccess);
+
+    fn init_fetch(world: &World) -> Self::Fetch<'_>;
+
+    fn candidates<'w>(_fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]> {
+        None
+    }
+
+    fn matches(fetch: &Self::Fetch<'_>, entity: Entity) -> bool;
+}
+
+/// Only matches entities that have a `C`.
+pub struct With<C>(PhantomData<C>);
+
+/// Only matches entities that don't have a `C`.
+pub struct Without<C>(PhantomData<C>);
+
+pub struct WriteFetch<'w, C> {
+    index: &'w SparseIndex,
+    dense: *mut C,
+}
+
+unsafe impl<C: Component> QueryData for &C {
+    type Item<'a> = &'a C;
+    type Fetch<'w> = Option<&'w SparseSet<C>>;
+
+    fn access(access: &mut ComponentAccess) {
+        access.add_read::<C>();
+    }
+
+    unsafe fn init_fetch(world: &World) -> Self::Fetch<'_> {
+        world.storage::<C>()
+    }
+
+    fn candidates<'w>(fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]> {
+        Some(fetch.map_or(&[], |storage| storage.index().entities()))
+    }
+
+    unsafe fn fetch<'a>(fetch: &'a Self::Fetch<'_>, entity: Entity) -> Option<Self::Item<'a>> {
+        fetch.and_then(|storage| storage.get(entity))
+    }
+}
+
+unsafe impl<C: Component> ReadOnlyQueryData for &C {}
+
+unsafe impl<C: Component> QueryData for &mut C {
+    type Item<'a> = &'a mut C;
+    type Fetch<'w> = Option<WriteFetch<'w, C>>;
+
+    fn access(access: &mut ComponentAccess) {
+        access.add_write::<C>();
+    }
+
+    unsafe fn init_fetch(world: &World) -> Self::Fetch<'_> {
+        world.storage_unchecked_mut::<C>().map(|storage| {
+            let (index, dense) = storage.split_mut();
+            WriteFetch { index, dense }
+        })
+    }
+
+    fn candidates<'w>(fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]> {
+        Some(fetch.as_ref().map_or(&[], |fetch| fetch.index.entities()))
+    }
+
+    unsafe fn fetch<'a>(fetch: &'a Self::Fetch<'_>, entity: Entity) -> Option<Self::Item<'a>> {
+        let fetch = fetch.as_ref()?;
+        let slot = fetch.index.slot(entity)?;
+        Some(&mut *fetch.dense.add(slot))
+    }
+}
+
+unsafe impl<Q: QueryData> QueryData for Option<Q> {
+    type Item<'a> = Option<Q::Item<'a>>;
+    type Fetch<'w> = Q::Fetch<'w>;
+
+    fn access(access: &mut ComponentAccess) {
+        Q::access(access);
+    }
+
+    unsafe fn init_fetch(world: &World) -> Self::Fetch<'_> {
+        Q::init_fetch(world)
+    }
+
+    fn candidates<'w>(_fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]> {
+        None
+    }
+
+    unsafe fn fetch<'a>(fetch: &'a Self::Fetch<'_>, entity: Entity) -> Option<Self::Item<'a>> {
+        Some(Q::fetch(fetch, entity))
+    }
+}
+
+unsafe impl<Q: ReadOnlyQueryData> ReadOnlyQueryData for Option<Q> {}
+
+unsafe impl QueryData for Entity {
+    type Item<'a> = Entity;
+    type Fetch<'w> = ();
+
+    fn access(_access: &mut ComponentAccess) {}
+
+    unsafe fn init_fetch(_world: &World) -> Self::Fetch<'_> {}
+
+    fn candidates<'w>(_fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]> {
+        None
+    }
+
+    unsafe fn fetch<'a>(_fetch: &'a Self::Fetch<'_>, entity: Entity) -> Option<Self::Item<'a>> {
+        Some(entity)
+    }
+}
+
+unsafe impl ReadOnlyQueryData for Entity {}
+
+unsafe impl<C: Component> QueryFilter for With<C> {
+    type Fetch<'w> = Option<&'w SparseIndex>;
+
+    fn access(access: &mut ComponentAccess) {
+        access.add_read::<C>();
+    }
+
+    fn init_fetch(world: &World) -> Self::Fetch<'_> {
+        world.storage::<C>().map(|storage| storage.index())
+    }
+
+    fn candidates<'w>(fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]> {
+        Some(fetch.map_or(&[], |index| index.entities()))
+    }
+
+    fn matches(fetch: &Self::Fetch<'_>, entity: Entity) -> bool {
+        fetch.is_some_and(|index| index.contains(entity))
+    }
+}
+
+unsafe impl<C: Component> QueryFilter for Without<C> {
+    type Fetch<'w> = Option<&'w SparseIndex>;
+
+    fn access(access: &mut ComponentAccess) {
+        access.add_read::<C>();
+    }
+
+    fn init_fetch(world: &World) -> Self::Fetch<'_> {
+        world.storage::<C>().map(|storage| storage.index())
+    }
+
+    fn matches(fetch: &Self::Fetch<'_>, entity: Entity) -> bool {
+        !fetch.is_some_and(|index| index.contains(entity))
+    }
+}
+
+fn shortest<'w>(a: Option<&'w [Entity]>, b: Option<&'w [Entity]>) -> Option<&'w [Entity]> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if b.len() < a.len() { b } else { a }),
+        (a, b) => a.or(b),
+    }
+}
+
+macro_rules! impl_query_tuple {
+    ($($name:ident),*) => {
+        #[allow(non_snake_case, unused_variables, clippy::unused_unit)]
+        unsafe impl<$($name: QueryData),*> QueryData for ($($name,)*) {
+            type Item<'a> = ($($name::Item<'a>,)*);
+            type Fetch<'w> = ($($name::Fetch<'w>,)*);
+
+            fn access(access: &mut ComponentAccess) {
+                $($name::access(access);)*
+            }
+
+            unsafe fn init_fetch(world: &World) -> Self::Fetch<'_> {
+                ($($name::init_fetch(world),)*)
+            }
+
+            fn candidates<'w>(fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]> {
+                let ($($name,)*) = fetch;
+                let candidates = None;
+                $(let candidates = shortest(candidates, $name::candidates($name));)*
+                candidates
+            }
+
+            unsafe fn fetch<'a>(fetch: &'a Self::Fetch<'_>, entity: Entity) -> Option<Self::Item<'a>> {
+                let ($($name,)*) = fetch;
+                Some(($($name::fetch($name, entity)?,)*))
+            }
+        }
+
+        unsafe impl<$($name: ReadOnlyQueryData),*> ReadOnlyQueryData for ($($name,)*) {}
+
+        #[allow(non_snake_case, unused_variables, clippy::unused_unit)]
+        unsafe impl<$($name: QueryFilter),*> QueryFilter for ($($name,)*) {
+            type Fetch<'w> = ($($name::Fetch<'w>,)*);
+
+            fn access(access: &mut ComponentAccess) {
+                $($name::access(access);)*
+            }
+
+            fn init_fetch(world: &World) -> Self::Fetch<'_> {
+                ($($name::init_fetch(world),)*)
+            }
+
+            fn candidates<'w>(fetch: &Self::Fetch<'w>) -> Option<&'w [Entity]> {
+                let ($($name,)*) = fetch;
+                let candidates = None;
+                $(let candidates = shortest(candidates, $name::candidates($name));)*
+                candidates
+            }
+
+            fn matches(fetch: &Self::Fetch<'_>, entity: Entity) -> bool {
+                let ($($name,)*) = fetch;
+                true $(&& $name::matches($name, entity))*
+            }
+        }
+    };
+}
+
+impl_query_tuple!();
+impl_query_tuple!(A);
+impl_query_tuple!(A, B);
+impl_query_tuple!(A, B, C);
+impl_query_tuple!(A, B, C, D);
+impl_query_tuple!(A, B, C, D, E);
+impl_query_tuple!(A, B, C, D, E, F);
+impl_query_tuple!(A, B, C, D, E, F, G);
+impl_query_tuple!(A, B, C, D, E, F, G, H);
+impl_query_tuple!(A, B, C, D, E, F, G, H, I);
+impl_query_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_query_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_query_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Iterates entities matching the component signature `Q` and the filter `F`.
+///
+/// Created through [`World::query`].
+pub struct Query<'w, Q: QueryData, F: QueryFilter = ()> {
+    world: &'w World,
+    fetch: Q::Fetch<'w>,
+    filter: F::Fetch<'w>,
+}
+
+impl<'w, Q: QueryData, F: QueryFilter> Query<'w, Q, F> {
+    /// # Safety
+    /// The access of `Q` and `F` must be valid for `'w`.
+    pub(crate) unsafe fn new(world: &'w World) -> Self {
+        Self {
+            world,
+            fetch: Q::init_fetch(world),
+            filter: F::init_fetch(world),
+        }
+    }
+
+    pub fn iter(&self) -> QueryIter<'_, 'w, Q, F>
+    where
+        Q: ReadOnlyQueryData,
+    {
+        QueryIter::new(self)
+    }
+
+    pub fn iter_mut(&mut self) -> QueryIter<'_, 'w, Q, F> {
+        QueryIter::new(self)
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<Q::Item<'_>>
+    where
+        Q: ReadOnlyQueryData,
+    {
+        unsafe { self.get_unchecked(entity) }
+    }
+
+    pub fn get_mut(&mut self, entity: Entity) -> Option<Q::Item<'_>> {
+        unsafe { self.get_unchecked(entity) }
+    }
+
+    unsafe fn get_unchecked(&self, entity: Entity) -> Option<Q::Item<'_>> {
+        if !F::matches(&self.filter, entity) {
+            return None;
+        }
+        Q::fetch(&self.fetch, entity)
+    }
+}
+
+impl<'q, 'w, Q: ReadOnlyQueryData, F: QueryFilter> IntoIterator for &'q Query<'w, Q, F> {
+    type Item = Q::Item<'q>;
+    type IntoIter = QueryIter<'q, 'w, Q, F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'q, 'w, Q: QueryData, F: QueryFilter> IntoIterator for &'q mut Query<'w, Q, F> {
+    type Item = Q::Item<'q>;
+    type IntoIter = QueryIter<'q, 'w, Q, F>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+enum Candidates<'a> {
+    Slice(std::slice::Iter<'a, Entity>),
+    All(Box<dyn Iterator<Item = Entity> + 'a>),
+}
+
+pub struct QueryIter<'q, 'w, Q: QueryData, F: QueryFilter> {
+    query: &'q Query<'w, Q, F>,
+    candidates: Candidates<'q>,
+}
+
+impl<'q, 'w, Q: QueryData, F: QueryFilter> QueryIter<'q, 'w, Q, F> {
+    fn new(query: &'q Query<'w, Q, F>) -> Self {
+        let candidates = match shortest(Q::candidates(&query.fetch), F::candidates(&query.filter)) {
+            Some(entities) => Candidates::Slice(entities.iter()),
+            None => Candidates::All(Box::new(query.world.entity_allocator().iter())),
+        };
+
+        Self { query, candidates }
+    }
+}
+
+impl<'q, 'w, Q: QueryData, F: QueryFilter> Iterator for QueryIter<'q, 'w, Q, F> {
+    type Item = Q::Item<'q>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entity = match &mut self.candidates {
+                Candidates::Slice(entities) => *entities.next()?,
+                Candidates::All(entities) => entities.next()?,
+            };
+
+            // Every entity comes up at most once, so mutable items never alias.
+            if let Some(item) = unsafe { self.query.get_unchecked(entity) } {
+                return Some(item);
+            }
+        }
+    }
+}
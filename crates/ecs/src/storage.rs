@@ -0,0 +1,167 @@
+use std::{any::Any, cell::UnsafeCell};
+
+use crate::component::Component;
+use crate::entity::Entity;
+
+/// Type erased view of a [`SparseSet`] so the world can hold one per component type.
+pub(crate) trait ComponentStorage: Send + Sync {
+    fn remove_entity(&mut self, entity: Entity) -> bool;
+    fn contains(&self, entity: Entity) -> bool;
+    fn len(&self) -> usize;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Holds a storage so queries can reach into it through a shared reference to the world.
+///
+/// Nothing here checks for aliasing, callers have to validate their access up front.
+pub(crate) struct StorageCell(UnsafeCell<Box<dyn ComponentStorage>>);
+
+// Safety: the storages themselves are Send + Sync, and every path that hands out a mutable
+// reference from a shared one is an unsafe function that requires the caller to have checked
+// its access.
+unsafe impl Sync for StorageCell {}
+
+impl StorageCell {
+    pub fn new<C: Component>() -> Self {
+        Self(UnsafeCell::new(Box::<SparseSet<C>>::default()))
+    }
+
+    pub fn get(&self) -> &dyn ComponentStorage {
+        unsafe { &**self.0.get() }
+    }
+
+    pub(crate) fn get_mut(&mut self) -> &mut dyn ComponentStorage {
+        &mut **self.0.get_mut()
+    }
+
+    /// # Safety
+    /// Nothing else may access this storage for as long as the returned reference lives.
+    #[allow(clippy::mut_from_ref)]
+    pub unsafe fn get_unchecked_mut(&self) -> &mut dyn ComponentStorage {
+        &mut **self.0.get()
+    }
+}
+
+/// Maps entities to slots in the dense arrays of a [`SparseSet`].
+pub struct SparseIndex {
+    sparse: Vec<Option<u32>>,
+    entities: Vec<Entity>,
+}
+
+impl SparseIndex {
+    pub(crate) fn slot(&self, entity: Entity) -> Option<usize> {
+        let slot = (*self.sparse.get(entity.index() as usize)?)? as usize;
+        (self.entities[slot] == entity).then_some(slot)
+    }
+
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.slot(entity).is_some()
+    }
+
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+}
+
+/// Components of a single type packed densely, with a sparse index from entity to slot.
+///
+/// Removal swaps the last component into the hole, so the dense arrays never have gaps.
+pub struct SparseSet<C> {
+    index: SparseIndex,
+    dense: Vec<C>,
+}
+
+impl<C> Default for SparseSet<C> {
+    fn default() -> Self {
+        Self {
+            index: SparseIndex {
+                sparse: Vec::new(),
+                entities: Vec::new(),
+            },
+            dense: Vec::new(),
+        }
+    }
+}
+
+impl<C: Component> SparseSet<C> {
+    pub(crate) fn insert(&mut self, entity: Entity, component: C) -> Option<C> {
+        if let Some(slot) = self.index.slot(entity) {
+            return Some(std::mem::replace(&mut self.dense[slot], component));
+        }
+
+        let index = entity.index() as usize;
+        if index >= self.index.sparse.len() {
+            self.index.sparse.resize(index + 1, None);
+        }
+        self.index.sparse[index] = Some(self.dense.len() as u32);
+        self.index.entities.push(entity);
+        self.dense.push(component);
+        None
+    }
+
+    pub(crate) fn remove(&mut self, entity: Entity) -> Option<C> {
+        let slot = self.index.slot(entity)?;
+        self.index.sparse[entity.index() as usize] = None;
+
+        let last = self.index.entities.len() - 1;
+        if slot != last {
+            let moved = self.index.entities[last];
+            self.index.sparse[moved.index() as usize] = Some(slot as u32);
+        }
+        self.index.entities.swap_remove(slot);
+        Some(self.dense.swap_remove(slot))
+    }
+
+    pub fn get(&self, entity: Entity) -> Option<&C> {
+        self.index.slot(entity).map(|slot| &self.dense[slot])
+    }
+
+    pub(crate) fn get_mut(&mut self, entity: Entity) -> Option<&mut C> {
+        self.index.slot(entity).map(|slot| &mut self.dense[slot])
+    }
+
+    pub fn index(&self) -> &SparseIndex {
+        &self.index
+    }
+
+    /// Splits off the index so components can be written through the pointer while the index
+    /// is still being read.
+    pub(crate) fn split_mut(&mut self) -> (&SparseIndex, *mut C) {
+        (&self.index, self.dense.as_mut_ptr())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, &C)> {
+        self.index.entities.iter().copied().zip(self.dense.iter())
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> impl Iterator<Item = (Entity, &mut C)> {
+        self.index
+            .entities
+            .iter()
+            .copied()
+            .zip(self.dense.iter_mut())
+    }
+}
+
+impl<C: Component> ComponentStorage for SparseSet<C> {
+    fn remove_entity(&mut self, entity: Entity) -> bool {
+        self.remove(entity).is_some()
+    }
+
+    fn contains(&self, entity: Entity) -> bool {
+        self.index.contains(entity)
+    }
+
+    fn len(&self) -> usize {
+        self.dense.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
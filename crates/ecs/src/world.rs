@@ -1,19 +1,177 @@
-use std::{
-    any::{Any, TypeId},
-    collections::HashMap,
-};
+use std::{any::TypeId, collections::HashMap};
 
-use crate::entity::Entity;
+use crate::access::ComponentAccess;
+use crate::component::Component;
+use crate::entity::{Entity, EntityAllocator};
+use crate::query::{Query, QueryData, QueryFilter};
+use crate::storage::{SparseSet, StorageCell};
 
 #[derive(Default)]
 pub struct World {
-    next_uuid: u64,
-    entities: Vec<Entity>,
-    components: HashMap<(Entity, TypeId), Box<dyn Any>>,
+    entities: EntityAllocator,
+    storages: HashMap<TypeId, StorageCell>,
 }
 
 impl World {
     pub fn new() -> Self {
         Self::default()
     }
+
+    pub fn spawn(&mut self) -> Entity {
+        self.entities.alloc()
+    }
+
+    /// Removes the entity and all of its components. Returns false if the handle was stale.
+    pub fn despawn(&mut self, entity: Entity) -> bool {
+        if !self.entities.free(entity) {
+            return false;
+        }
+
+        for storage in self.storages.values_mut() {
+            storage.get_mut().remove_entity(entity);
+        }
+        true
+    }
+
+    pub fn is_alive(&self, entity: Entity) -> bool {
+        self.entities.is_alive(entity)
+    }
+
+    pub fn entity_count(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter()
+    }
+
+    /// Adds the component to the entity, returning the one it replaced.
+    ///
+    /// # Panics
+    /// If the entity has been despawned.
+    pub fn insert<C: Component>(&mut self, entity: Entity, component: C) -> Option<C> {
+        assert!(
+            self.is_alive(entity),
+            "Tried inserting {} into dead entity {entity:?}",
+            std::any::type_name::<C>()
+        );
+
+        self.storage_mut_or_default::<C>().insert(entity, component)
+    }
+
+    pub fn remove<C: Component>(&mut self, entity: Entity) -> Option<C> {
+        self.storage_mut::<C>()?.remove(entity)
+    }
+
+    pub fn get<C: Component>(&self, entity: Entity) -> Option<&C> {
+        self.storage::<C>()?.get(entity)
+    }
+
+    pub fn get_mut<C: Component>(&mut self, entity: Entity) -> Option<&mut C> {
+        self.storage_mut::<C>()?.get_mut(entity)
+    }
+
+    pub fn has<C: Component>(&self, entity: Entity) -> bool {
+        self.storages
+            .get(&TypeId::of::<C>())
+            .is_some_and(|storage| storage.get().contains(entity))
+    }
+
+    /// Number of entities that currently have a `C`.
+    pub fn component_count<C: Component>(&self) -> usize {
+        self.storages
+            .get(&TypeId::of::<C>())
+            .map(|storage| storage.get().len())
+            .unwrap_or_default()
+    }
+
+    pub fn iter<C: Component>(&self) -> impl Iterator<Item = (Entity, &C)> {
+        self.storage::<C>()
+            .into_iter()
+            .flat_map(|storage| storage.iter())
+    }
+
+    pub fn iter_mut<C: Component>(&mut self) -> impl Iterator<Item = (Entity, &mut C)> {
+        self.storage_mut::<C>()
+            .into_iter()
+            .flat_map(|storage| storage.iter_mut())
+    }
+
+    /// Iterates every entity that has the components in `Q`.
+    ///
+    /// # Panics
+    /// If `Q` accesses a component mutably more than once, or both mutably and immutably.
+    pub fn query<Q: QueryData>(&mut self) -> Query<'_, Q> {
+        self.query_filtered::<Q, ()>()
+    }
+
+    /// Like [`World::query`], but also only matching entities that pass the filter `F`.
+    pub fn query_filtered<Q: QueryData, F: QueryFilter>(&mut self) -> Query<'_, Q, F> {
+        let access = Self::query_access::<Q, F>();
+        let conflicts = access.self_conflicts();
+        assert!(
+            conflicts.is_empty(),
+            "Query {} aliases mutable access to {}",
+            std::any::type_name::<Q>(),
+            conflicts.join(", ")
+        );
+
+        unsafe { Query::new(self) }
+    }
+
+    /// Like [`World::query_filtered`] but through a shared reference, so several queries can be alive at
+    /// once.
+    ///
+    /// # Safety
+    /// The access of `Q` (see [`World::query_access`]) must not conflict with any other query
+    /// or component reference alive at the same time, including its own.
+    pub unsafe fn query_unchecked<Q: QueryData, F: QueryFilter>(&self) -> Query<'_, Q, F> {
+        Query::new(self)
+    }
+
+    pub fn query_access<Q: QueryData, F: QueryFilter>() -> ComponentAccess {
+        let mut access = ComponentAccess::new();
+        Q::access(&mut access);
+        F::access(&mut access);
+        access
+    }
+
+    pub(crate) fn entity_allocator(&self) -> &EntityAllocator {
+        &self.entities
+    }
+
+    pub(crate) fn storage<C: Component>(&self) -> Option<&SparseSet<C>> {
+        self.storages
+            .get(&TypeId::of::<C>())
+            .map(|storage| storage.get().as_any().downcast_ref().unwrap())
+    }
+
+    /// # Safety
+    /// Nothing else may access the `C` storage while the returned reference lives.
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) unsafe fn storage_unchecked_mut<C: Component>(&self) -> Option<&mut SparseSet<C>> {
+        self.storages.get(&TypeId::of::<C>()).map(|storage| {
+            storage
+                .get_unchecked_mut()
+                .as_any_mut()
+                .downcast_mut()
+                .unwrap()
+        })
+    }
+
+    fn storage_mut<C: Component>(&mut self) -> Option<&mut SparseSet<C>> {
+        self.storages
+            .get_mut(&TypeId::of::<C>())
+            .map(|storage| storage.get_mut().as_any_mut().downcast_mut().unwrap())
+    }
+
+    fn storage_mut_or_default<C: Component>(&mut self) -> &mut SparseSet<C> {
+        self.storages
+            .entry(TypeId::of::<C>())
+            .or_insert_with(StorageCell::new::<C>)
+            .get_mut()
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
+    }
 }
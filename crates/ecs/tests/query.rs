@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod test {
+    use ecs::{Entity, With, Without, World};
+
+    #[derive(Debug, PartialEq)]
+    struct Position(i32);
+
+    #[derive(Debug, PartialEq)]
+    struct Velocity(i32);
+
+    #[derive(Debug, PartialEq)]
+    struct Name(&'static str);
+
+    struct Frozen;
+
+    fn get_world() -> (World, Vec<Entity>) {
+        let mut world = World::new();
+        let mut entities = Vec::new();
+        for i in 0..4 {
+            let entity = world.spawn();
+            world.insert(entity, Position(i));
+            if i % 2 == 0 {
+                world.insert(entity, Velocity(10));
+            }
+            entities.push(entity);
+        }
+        world.insert(entities[0], Name("first"));
+        world.insert(entities[2], Frozen);
+        (world, entities)
+    }
+
+    #[test]
+    fn mutable_and_optional_components() {
+        let (mut world, entities) = get_world();
+
+        let mut query = world.query::<(Entity, &mut Position, &Velocity, Option<&Name>)>();
+        let mut seen = Vec::new();
+        for (entity, position, velocity, name) in query.iter_mut() {
+            position.0 += velocity.0;
+            seen.push((entity, name.map(|name| name.0)));
+        }
+        seen.sort();
+
+        assert_eq!(
+            seen,
+            vec![(entities[0], Some("first")), (entities[2], None)]
+        );
+        assert_eq!(world.get::<Position>(entities[0]), Some(&Position(10)));
+        assert_eq!(world.get::<Position>(entities[1]), Some(&Position(1)));
+    }
+
+    #[test]
+    fn filters() {
+        let (mut world, entities) = get_world();
+
+        let query = world.query_filtered::<Entity, (With<Velocity>, Without<Frozen>)>();
+        assert_eq!(query.iter().collect::<Vec<_>>(), vec![entities[0]]);
+
+        let query = world.query_filtered::<&Position, Without<Velocity>>();
+        let mut positions: Vec<_> = query.iter().map(|position| position.0).collect();
+        positions.sort();
+        assert_eq!(positions, vec![1, 3]);
+        assert_eq!(query.get(entities[0]), None);
+        assert_eq!(query.get(entities[1]), Some(&Position(1)));
+    }
+
+    #[test]
+    fn missing_components_match_nothing() {
+        let (mut world, _) = get_world();
+
+        assert_eq!(world.query::<(&Position, &Frozen)>().iter().count(), 1);
+        assert_eq!(world.query::<&String>().iter().count(), 0);
+        assert_eq!(world.query::<Option<&String>>().iter().count(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "aliases mutable access")]
+    fn aliasing_mutable_access_panics() {
+        let (mut world, _) = get_world();
+        world.query::<(&mut Position, &Position)>();
+    }
+
+    #[test]
+    #[should_panic(expected = "aliases mutable access")]
+    fn aliasing_through_option_panics() {
+        let (mut world, _) = get_world();
+        world.query::<(&mut Velocity, Option<&mut Velocity>)>();
+    }
+
+    #[test]
+    #[should_panic(expected = "aliases mutable access")]
+    fn aliasing_through_a_filter_panics() {
+        let (mut world, _) = get_world();
+        world.query_filtered::<&mut Velocity, With<Velocity>>();
+    }
+}
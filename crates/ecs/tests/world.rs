@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod test {
+    use ecs::World;
+
+    #[derive(Debug, PartialEq)]
+    struct Position(f32, f32);
+
+    #[derive(Debug, PartialEq)]
+    struct Health(u32);
+
+    #[test]
+    fn insert_and_get_components() {
+        let mut world = World::new();
+        let entity = world.spawn();
+
+        assert_eq!(world.insert(entity, Position(1.0, 2.0)), None);
+        world.insert(entity, Health(10));
+
+        assert_eq!(world.get::<Position>(entity), Some(&Position(1.0, 2.0)));
+        world.get_mut::<Health>(entity).unwrap().0 -= 3;
+        assert_eq!(world.get::<Health>(entity), Some(&Health(7)));
+
+        assert_eq!(world.insert(entity, Health(1)), Some(Health(7)));
+    }
+
+    #[test]
+    fn remove_and_has() {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.insert(entity, Health(5));
+
+        assert!(world.has::<Health>(entity));
+        assert!(!world.has::<Position>(entity));
+        assert_eq!(world.remove::<Health>(entity), Some(Health(5)));
+        assert!(!world.has::<Health>(entity));
+        assert_eq!(world.remove::<Health>(entity), None);
+    }
+
+    #[test]
+    fn stale_handles_do_not_alias_reused_slots() {
+        let mut world = World::new();
+        let old = world.spawn();
+        world.insert(old, Health(1));
+
+        assert!(world.despawn(old));
+        assert!(!world.despawn(old));
+
+        let new = world.spawn();
+        assert_eq!(old.index(), new.index());
+        assert_ne!(old, new);
+        world.insert(new, Health(2));
+
+        assert!(!world.is_alive(old));
+        assert_eq!(world.get::<Health>(old), None);
+        assert_eq!(world.get::<Health>(new), Some(&Health(2)));
+        assert_eq!(world.entity_count(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn inserting_into_dead_entity() {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.despawn(entity);
+        world.insert(entity, Health(1));
+    }
+
+    #[test]
+    fn iteration_survives_swap_removal() {
+        let mut world = World::new();
+        let entities: Vec<_> = (0..4)
+            .map(|i| {
+                let entity = world.spawn();
+                world.insert(entity, Health(i));
+                entity
+            })
+            .collect();
+
+        world.despawn(entities[1]);
+        world.remove::<Health>(entities[0]);
+        for (_, health) in world.iter_mut::<Health>() {
+            health.0 *= 10;
+        }
+
+        let mut seen: Vec<_> = world.iter::<Health>().map(|(e, h)| (e, h.0)).collect();
+        seen.sort();
+        assert_eq!(seen, vec![(entities[2], 20), (entities[3], 30)]);
+        assert_eq!(world.component_count::<Health>(), 2);
+        assert_eq!(world.get::<Health>(entities[3]), Some(&Health(30)));
+    }
+}
@@ -2,6 +2,7 @@ use std::fmt::Display;
 
 pub type EngineResult<T> = std::result::Result<T, EngineError>;
 
+#[derive(Debug)]
 pub struct EngineError {
     error_id: &'static str,
     error_msg: String,
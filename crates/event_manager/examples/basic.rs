@@ -30,11 +30,15 @@ fn main() {
     );
 
     println!(
-        "All KeyboardEvents: {:?}",
-        ev.take_all_of_type::<KeyboardEvent>()
+        "Remaining KeyboardEvents: {:?}",
+        ev.iter::<KeyboardEvent>().collect::<Vec<_>>()
     );
     println!(
-        "All MouseMovedEvents: {:?}",
-        ev.take_all_of_type::<MouseMovedEvent>()
+        "Drained KeyboardEvents: {:?}",
+        ev.drain::<KeyboardEvent>().collect::<Vec<_>>()
+    );
+    println!(
+        "KeyboardEvent count after draining: {}",
+        ev.get_event_count::<KeyboardEvent>()
     );
 }
@@ -0,0 +1,144 @@
+use std::any::Any;
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Mutex;
+
+use crate::observer::{ObserverList, Observers};
+use crate::Event;
+
+pub(crate) struct EventInstance<T> {
+    pub id: usize,
+    pub event: T,
+}
+
+/// Type-erased access to an [`Events`] queue, for the parts of the event manager that have to
+/// touch every queue regardless of its type.
+pub(crate) trait EventQueue: Send + Sync {
+    /// Swaps the buffers, then queues what was sent through the channel since the last update,
+    /// calling `observers` for each of those events.
+    fn update(&mut self, observers: Option<&mut (dyn ObserverList + 'static)>);
+    fn clear(&mut self);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// Two buffers of events, one for the previous frame and one for the current frame. Every
+/// update drops the older buffer, so an event lives for the frame it was sent in and the one
+/// after, which is long enough for every system to see it once regardless of system order.
+pub(crate) struct Events<T> {
+    older: VecDeque<EventInstance<T>>,
+    newer: VecDeque<EventInstance<T>>,
+    sent: usize,
+    /// Created on the first call to [`Events::sender`]. The receiver is only touched through
+    /// `&mut self`, the mutex just keeps the queue `Sync`.
+    channel: Option<(Sender<T>, Mutex<Receiver<T>>)>,
+}
+
+impl<T> Default for Events<T> {
+    fn default() -> Self {
+        Self {
+            older: VecDeque::new(),
+            newer: VecDeque::new(),
+            sent: 0,
+            channel: None,
+        }
+    }
+}
+
+impl<T: Event> Events<T> {
+    /// The id the next event will get. Every event sent so far has a lower one.
+    pub fn sent(&self) -> usize {
+        self.sent
+    }
+
+    pub fn push_back(&mut self, event: T) {
+        let id = self.next_id();
+        self.newer.push_back(EventInstance { id, event });
+    }
+
+    pub fn push_front(&mut self, event: T) {
+        let id = self.next_id();
+        let buffer = if self.older.is_empty() {
+            &mut self.newer
+        } else {
+            &mut self.older
+        };
+        buffer.push_front(EventInstance { id, event });
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.older
+            .pop_front()
+            .or_else(|| self.newer.pop_front())
+            .map(|instance| instance.event)
+    }
+
+    pub fn len(&self) -> usize {
+        self.older.len() + self.newer.len()
+    }
+
+    /// Oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &EventInstance<T>> {
+        self.older.iter().chain(self.newer.iter())
+    }
+
+    /// Oldest first.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.older
+            .drain(..)
+            .chain(self.newer.drain(..))
+            .map(|instance| instance.event)
+    }
+
+    pub fn sender(&mut self) -> Sender<T> {
+        let (sender, _) = self.channel.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::channel();
+            (sender, Mutex::new(receiver))
+        });
+        sender.clone()
+    }
+
+    fn next_id(&mut self) -> usize {
+        let id = self.sent;
+        self.sent += 1;
+        id
+    }
+}
+
+impl<T: Event> EventQueue for Events<T> {
+    fn update(&mut self, observers: Option<&mut (dyn ObserverList + 'static)>) {
+        std::mem::swap(&mut self.older, &mut self.newer);
+        self.newer.clear();
+
+        let Some((_, receiver)) = &mut self.channel else {
+            return;
+        };
+        let received: Vec<_> = receiver.get_mut().unwrap().try_iter().collect();
+
+        let mut observers = observers.map(|observers| {
+            observers
+                .as_any_mut()
+                .downcast_mut::<Observers<T>>()
+                .unwrap()
+        });
+        for event in received {
+            if let Some(observers) = &mut observers {
+                observers.notify(&event);
+            }
+            self.push_back(event);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.older.clear();
+        self.newer.clear();
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
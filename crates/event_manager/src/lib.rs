@@ -1,12 +1,47 @@
-use std::any::Any;
-use std::collections::{HashMap, VecDeque};
+mod events;
+pub mod observer;
+pub mod reader;
+pub mod record;
+pub mod sender;
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::time::Duration;
+
+use crate::events::{EventQueue, Events};
+use crate::observer::{ObserverList, Observers};
+use crate::record::Recorder;
+
+pub use crate::observer::Subscription;
+pub use crate::reader::{EventCursor, EventReader};
+pub use crate::record::{RecordableEvent, Recording};
+pub use crate::sender::EventSender;
+
+/// Events can be read from systems running on other threads, and sent from any thread through an
+/// [`EventSender`].
+pub trait Event: Send + Sync + 'static {}
+
+impl<T: Send + Sync + 'static> Event for T {}
 
 #[derive(Eq, PartialEq, Hash)]
-struct EventID(std::any::TypeId);
+struct EventID(TypeId);
 
+/// Per-type event queues.
+///
+/// Events are kept until two calls to [`EventManager::update`] have passed, which `App` does once
+/// per frame. Any number of [`EventCursor`]s can read them in the meantime, each seeing every
+/// event once. [`EventManager::get_event`] takes an event out for everyone instead.
+///
+/// Handlers added with [`EventManager::subscribe`] don't wait for anyone to read the queue, they
+/// are called as soon as an event is added.
 #[derive(Default)]
 pub struct EventManager {
-    events: HashMap<EventID, VecDeque<Box<dyn Any>>>,
+    events: HashMap<EventID, Box<dyn EventQueue>>,
+    observers: HashMap<EventID, Box<dyn ObserverList>>,
+    next_subscription: u64,
+    recorder: Option<Recorder>,
+    frame: u64,
 }
 
 impl EventManager {
@@ -14,54 +49,194 @@ impl EventManager {
         Self::default()
     }
 
-    pub fn add<T: Any>(&mut self, event: T) {
-        let id = self.get_event_id::<T>();
+    /// Swaps the buffers of every queue, dropping the events sent before the previous update, and
+    /// queues the events sent through [`EventSender`]s since then.
+    pub fn update(&mut self) {
+        if let Some(recorder) = &self.recorder {
+            recorder.set_receiving(true);
+        }
+        for (id, queue) in &mut self.events {
+            let observers = self.observers.get_mut(id);
+            queue.update(observers.map(|observers| observers.as_mut()));
+        }
 
-        self.events
-            .entry(id)
-            .or_default()
-            .push_back(Box::new(event));
+        if let Some(recorder) = &mut self.recorder {
+            recorder.set_receiving(false);
+            recorder.write_frame(self.frame);
+        }
+        self.frame += 1;
     }
 
-    pub fn return_event<T: Any>(&mut self, event: T) {
-        let id = self.get_event_id::<T>();
+    /// How many times [`EventManager::update`] has been called.
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
 
-        self.events
-            .entry(id)
-            .or_default()
-            .push_front(Box::new(event));
+    /// Starts writing the events of the types passed to [`EventManager::record`] to `writer`, so
+    /// they can be played back later through a [`Recording`]. Only record events that come from
+    /// outside the app, like input, as events sent by systems will be sent again on replay.
+    pub fn start_recording(&mut self, writer: impl Write + Send + 'static) -> io::Result<()> {
+        if self.recorder.is_some() {
+            self.stop_recording()?;
+        }
+
+        self.recorder = Some(Recorder::new(Box::new(writer))?);
+        Ok(())
     }
 
-    pub fn get_event_count<T: Any>(&self) -> usize {
+    /// Adds `T` to the recorded types.
+    pub fn record<T: RecordableEvent>(&mut self) {
+        let handler = self
+            .recorder
+            .as_ref()
+            .expect("start_recording has to be called before choosing what to record")
+            .handler::<T>();
+        let subscription = self.subscribe_with_priority(i32::MIN, handler);
+        self.recorder
+            .as_mut()
+            .unwrap()
+            .add_subscription(subscription);
+    }
+
+    /// Adds how long the coming update takes to the recording, if there is one, so a replay can
+    /// advance time by the same amount. `App` does this on every update.
+    pub fn record_frame_delta(&mut self, delta: Duration) {
+        if let Some(recorder) = &self.recorder {
+            recorder.add_frame_delta(delta);
+        }
+    }
+
+    /// Writes out whatever is left and returns the first error writing the recording ran into.
+    pub fn stop_recording(&mut self) -> io::Result<()> {
+        let Some(recorder) = self.recorder.take() else {
+            return Ok(());
+        };
+
+        let (subscriptions, result) = recorder.finish(self.frame);
+        for subscription in subscriptions {
+            self.unsubscribe(subscription);
+        }
+        result
+    }
+
+    /// A handle for sending events of type `T` from other threads.
+    pub fn sender<T: Event>(&mut self) -> EventSender<T> {
+        EventSender::new(self.queue_mut::<T>().sender())
+    }
+
+    /// Calls the handlers subscribed to `T` with the event, then queues it.
+    pub fn add<T: Event>(&mut self, event: T) {
         let id = self.get_event_id::<T>();
-        self.events
-            .get(&id)
-            .map(|events| events.len())
+        if let Some(observers) = self.observers.get_mut(&id) {
+            observers
+                .as_any_mut()
+                .downcast_mut::<Observers<T>>()
+                .unwrap()
+                .notify(&event);
+        }
+
+        self.queue_mut::<T>().push_back(event);
+    }
+
+    /// Calls `handler` with every event of type `T` added from now on, right when it is added.
+    pub fn subscribe<T: Event>(
+        &mut self,
+        handler: impl FnMut(&T) + Send + Sync + 'static,
+    ) -> Subscription {
+        self.subscribe_with_priority(0, handler)
+    }
+
+    /// Like [`EventManager::subscribe`], but handlers with a higher priority are called first.
+    /// Handlers with the same priority are called in the order they subscribed in.
+    pub fn subscribe_with_priority<T: Event>(
+        &mut self,
+        priority: i32,
+        handler: impl FnMut(&T) + Send + Sync + 'static,
+    ) -> Subscription {
+        let id = self.next_subscription;
+        self.next_subscription += 1;
+
+        self.observers
+            .entry(self.get_event_id::<T>())
+            .or_insert_with(|| Box::<Observers<T>>::default())
+            .as_any_mut()
+            .downcast_mut::<Observers<T>>()
+            .unwrap()
+            .insert(id, priority, Box::new(handler));
+
+        Subscription {
+            event: TypeId::of::<T>(),
+            id,
+        }
+    }
+
+    /// Returns false if the handler was already unsubscribed.
+    pub fn unsubscribe(&mut self, subscription: Subscription) -> bool {
+        self.observers
+            .get_mut(&EventID(subscription.event))
+            .is_some_and(|observers| observers.remove(subscription.id))
+    }
+
+    /// Puts an event back at the front of its queue. Cursors see it as a new event, but
+    /// subscribed handlers aren't called again.
+    pub fn return_event<T: Event>(&mut self, event: T) {
+        self.queue_mut::<T>().push_front(event);
+    }
+
+    pub fn get_event_count<T: Event>(&self) -> usize {
+        self.queue::<T>()
+            .map(|queue| queue.len())
             .unwrap_or_default()
     }
 
-    pub fn get_event<T: Any>(&mut self) -> Option<T> {
+    /// Takes the oldest event of type `T` out, so no one else will see it.
+    pub fn get_event<T: Event>(&mut self) -> Option<T> {
+        self.queue_mut::<T>().pop_front()
+    }
+
+    /// Takes every event of type `T` out, oldest first. Events not yet iterated over when the
+    /// iterator is dropped are removed all the same.
+    pub fn drain<T: Event>(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.queue_mut::<T>().drain()
+    }
+
+    /// Every event of type `T` still around, oldest first, without taking them out.
+    pub fn iter<T: Event>(&self) -> impl Iterator<Item = &T> {
+        self.queue::<T>()
+            .into_iter()
+            .flat_map(|queue| queue.iter())
+            .map(|instance| &instance.event)
+    }
+
+    pub fn clear<T: Event>(&mut self) {
+        self.queue_mut::<T>().clear();
+    }
+
+    /// Removes the events of every type. Subscriptions are kept.
+    pub fn clear_all(&mut self) {
+        for queue in self.events.values_mut() {
+            queue.clear();
+        }
+    }
+
+    pub(crate) fn queue<T: Event>(&self) -> Option<&Events<T>> {
         let id = self.get_event_id::<T>();
         self.events
-            .get_mut(&id)
-            .and_then(|events| events.pop_front())
-            .map(|event| *event.downcast().unwrap())
+            .get(&id)
+            .map(|queue| queue.as_any().downcast_ref().unwrap())
     }
 
-    pub fn take_all_of_type<T: Any>(&self) -> Vec<&T> {
+    fn queue_mut<T: Event>(&mut self) -> &mut Events<T> {
         let id = self.get_event_id::<T>();
         self.events
-            .get(&id)
-            .map(|events| {
-                events
-                    .iter()
-                    .map(|event| event.downcast_ref().unwrap())
-                    .collect()
-            })
-            .unwrap_or_default()
+            .entry(id)
+            .or_insert_with(|| Box::<Events<T>>::default())
+            .as_any_mut()
+            .downcast_mut()
+            .unwrap()
     }
 
-    fn get_event_id<T: Any>(&self) -> EventID {
-        EventID(std::any::TypeId::of::<T>())
+    fn get_event_id<T: Event>(&self) -> EventID {
+        EventID(TypeId::of::<T>())
     }
 }
@@ -0,0 +1,72 @@
+use std::any::{Any, TypeId};
+
+use crate::Event;
+
+/// Returned by [`EventManager::subscribe`](crate::EventManager::subscribe), and given back to
+/// [`EventManager::unsubscribe`](crate::EventManager::unsubscribe) to remove the handler again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Subscription {
+    pub(crate) event: TypeId,
+    pub(crate) id: u64,
+}
+
+struct Handler<T> {
+    id: u64,
+    priority: i32,
+    handler: Box<dyn FnMut(&T) + Send + Sync>,
+}
+
+/// Type-erased access to [`Observers`], so subscriptions can be removed without knowing the
+/// event type.
+pub(crate) trait ObserverList: Send + Sync {
+    fn remove(&mut self, id: u64) -> bool;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+/// The handlers subscribed to events of type `T`, sorted by descending priority.
+pub(crate) struct Observers<T> {
+    handlers: Vec<Handler<T>>,
+}
+
+impl<T> Default for Observers<T> {
+    fn default() -> Self {
+        Self {
+            handlers: Vec::new(),
+        }
+    }
+}
+
+impl<T: Event> Observers<T> {
+    pub fn insert(&mut self, id: u64, priority: i32, handler: Box<dyn FnMut(&T) + Send + Sync>) {
+        // After every handler with the same priority, so those run in subscription order.
+        let index = self
+            .handlers
+            .partition_point(|other| other.priority >= priority);
+        self.handlers.insert(
+            index,
+            Handler {
+                id,
+                priority,
+                handler,
+            },
+        );
+    }
+
+    pub fn notify(&mut self, event: &T) {
+        for handler in &mut self.handlers {
+            (handler.handler)(event);
+        }
+    }
+}
+
+impl<T: Event> ObserverList for Observers<T> {
+    fn remove(&mut self, id: u64) -> bool {
+        let len = self.handlers.len();
+        self.handlers.retain(|handler| handler.id != id);
+        self.handlers.len() != len
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
@@ -0,0 +1,101 @@
+use std::marker::PhantomData;
+
+use crate::{Event, EventManager};
+
+/// Remembers which events of type `T` have been read, so every reader sees each event once no
+/// matter how many other readers there are.
+pub struct EventCursor<T> {
+    next: usize,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for EventCursor<T> {
+    fn default() -> Self {
+        Self {
+            next: 0,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for EventCursor<T> {
+    fn clone(&self) -> Self {
+        Self {
+            next: self.next,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Event> EventCursor<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The events this cursor hasn't seen yet, oldest first. They are all marked as read right
+    /// away, even if the iterator isn't run to the end.
+    pub fn read<'a>(&mut self, events: &'a EventManager) -> impl Iterator<Item = &'a T> {
+        let next = self.next;
+        let queue = events.queue::<T>();
+        if let Some(queue) = queue {
+            self.next = queue.sent();
+        }
+
+        queue
+            .into_iter()
+            .flat_map(|queue| queue.iter())
+            .filter(move |instance| instance.id >= next)
+            .map(|instance| &instance.event)
+    }
+
+    pub fn len(&self, events: &EventManager) -> usize {
+        events
+            .queue::<T>()
+            .map(|queue| {
+                queue
+                    .iter()
+                    .filter(|instance| instance.id >= self.next)
+                    .count()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self, events: &EventManager) -> bool {
+        self.len(events) == 0
+    }
+
+    /// Marks every event currently in the manager as read.
+    pub fn clear(&mut self, events: &EventManager) {
+        if let Some(queue) = events.queue::<T>() {
+            self.next = queue.sent();
+        }
+    }
+}
+
+/// Reads events of type `T` through a cursor owned by someone else, usually a system.
+pub struct EventReader<'w, 's, T> {
+    events: &'w EventManager,
+    cursor: &'s mut EventCursor<T>,
+}
+
+impl<'w, 's, T: Event> EventReader<'w, 's, T> {
+    pub fn new(events: &'w EventManager, cursor: &'s mut EventCursor<T>) -> Self {
+        Self { events, cursor }
+    }
+
+    pub fn read(&mut self) -> impl Iterator<Item = &'w T> {
+        self.cursor.read(self.events)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cursor.len(self.events)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cursor.is_empty(self.events)
+    }
+
+    pub fn clear(&mut self) {
+        self.cursor.clear(self.events);
+    }
+}
@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::{Event, EventManager, Subscription};
+
+const HEADER: &str = "spyder-events 1";
+
+/// Stands in for the type name on lines holding how long a frame took, in nanoseconds.
+const FRAME_DELTA: &str = "@delta";
+
+/// Stands in for the type name on lines holding an event that came through an
+/// [`EventSender`](crate::EventSender), followed by the real name and data. Those are queued
+/// during the update, after the buffers were swapped, so replay has to do the same.
+const SENT: &str = "@sent";
+
+/// Events that can be written to a recording and read back for replay.
+pub trait RecordableEvent: Event + Sized {
+    /// Identifies the type in the recording, so it has to be unique among the recorded types,
+    /// can't contain whitespace and can't start with `@`.
+    const NAME: &'static str;
+
+    fn encode(&self) -> String;
+
+    fn decode(data: &str) -> Option<Self>;
+}
+
+/// Writes recorded events out, one line per event, each line holding the frame the event was
+/// added in, the name of its type and its encoded data.
+pub(crate) struct Recorder {
+    writer: Mutex<Box<dyn Write + Send>>,
+    pending: Arc<Mutex<Vec<String>>>,
+    /// Set while the event manager queues the events sent through senders.
+    receiving: Arc<AtomicBool>,
+    subscriptions: Vec<Subscription>,
+    error: Option<io::Error>,
+}
+
+impl Recorder {
+    pub fn new(mut writer: Box<dyn Write + Send>) -> io::Result<Self> {
+        writeln!(writer, "{HEADER}")?;
+        Ok(Self {
+            writer: Mutex::new(writer),
+            pending: Arc::default(),
+            receiving: Arc::default(),
+            subscriptions: Vec::new(),
+            error: None,
+        })
+    }
+
+    pub fn handler<T: RecordableEvent>(&self) -> impl FnMut(&T) + Send + Sync + 'static {
+        assert!(
+            !T::NAME.is_empty()
+                && !T::NAME.starts_with('@')
+                && !T::NAME.contains(char::is_whitespace),
+            "Recorded event names can't be empty, start with @ or contain whitespace, got \"{}\"",
+            T::NAME
+        );
+
+        let pending = self.pending.clone();
+        let receiving = self.receiving.clone();
+        move |event: &T| {
+            let mut line = format!("{} {}", T::NAME, escape(&event.encode()));
+            if receiving.load(Ordering::Relaxed) {
+                line = format!("{SENT} {line}");
+            }
+            pending.lock().unwrap().push(line);
+        }
+    }
+
+    pub fn set_receiving(&self, receiving: bool) {
+        self.receiving.store(receiving, Ordering::Relaxed);
+    }
+
+    pub fn add_frame_delta(&self, delta: Duration) {
+        let line = format!("{FRAME_DELTA} {}", delta.as_nanos());
+        self.pending.lock().unwrap().push(line);
+    }
+
+    pub fn add_subscription(&mut self, subscription: Subscription) {
+        self.subscriptions.push(subscription);
+    }
+
+    /// Writes the events recorded since the last call. Stops writing after the first error, which
+    /// is returned from [`Recorder::finish`].
+    pub fn write_frame(&mut self, frame: u64) {
+        let lines = std::mem::take(&mut *self.pending.lock().unwrap());
+        if self.error.is_some() || lines.is_empty() {
+            return;
+        }
+
+        let writer = self.writer.get_mut().unwrap();
+        let result = lines
+            .iter()
+            .try_for_each(|line| writeln!(writer, "{frame} {line}"))
+            .and_then(|()| writer.flush());
+        self.error = result.err();
+    }
+
+    pub fn finish(mut self, frame: u64) -> (Vec<Subscription>, io::Result<()>) {
+        self.write_frame(frame);
+        let result = match self.error {
+            Some(error) => Err(error),
+            None => self.writer.get_mut().unwrap().flush(),
+        };
+        (self.subscriptions, result)
+    }
+}
+
+/// Decodes an event and adds it to the event manager, or sends it through a sender if the flag
+/// is set.
+type Decoder = Box<dyn Fn(&str, bool, &mut EventManager) -> bool>;
+
+/// Events read back from a file written by [`EventManager::start_recording`], to be fed to an
+/// event manager frame by frame.
+///
+/// Every recorded type has to be registered before it can be played back.
+#[derive(Default)]
+pub struct Recording {
+    /// The name, data and whether the event came through a sender.
+    frames: BTreeMap<u64, Vec<(String, String, bool)>>,
+    deltas: BTreeMap<u64, Duration>,
+    decoders: HashMap<String, Decoder>,
+}
+
+impl Recording {
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read(BufReader::new(File::open(path)?))
+    }
+
+    pub fn read(reader: impl BufRead) -> io::Result<Self> {
+        let mut lines = reader.lines();
+        if lines.next().transpose()?.as_deref() != Some(HEADER) {
+            return Err(invalid_data("Not an event recording".to_string()));
+        }
+
+        let mut recording = Self::default();
+        for (number, line) in lines.enumerate() {
+            let line = line?;
+            let mut parts = line.splitn(3, ' ');
+            let (Some(frame), Some(name), Some(data)) = (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(invalid_data(format!("Malformed line {}", number + 2)));
+            };
+            let frame = frame
+                .parse()
+                .map_err(|_| invalid_data(format!("Malformed frame on line {}", number + 2)))?;
+
+            if name == FRAME_DELTA {
+                let nanos = data.parse().map_err(|_| {
+                    invalid_data(format!("Malformed frame delta on line {}", number + 2))
+                })?;
+                recording.deltas.insert(frame, Duration::from_nanos(nanos));
+                continue;
+            }
+
+            let (name, data, sent) = match name {
+                SENT => {
+                    let Some((name, data)) = data.split_once(' ') else {
+                        return Err(invalid_data(format!("Malformed line {}", number + 2)));
+                    };
+                    (name, data, true)
+                }
+                name => (name, data, false),
+            };
+            recording.frames.entry(frame).or_default().push((
+                name.to_string(),
+                unescape(data),
+                sent,
+            ));
+        }
+        Ok(recording)
+    }
+
+    pub fn register<T: RecordableEvent>(&mut self) -> &mut Self {
+        self.decoders.insert(
+            T::NAME.to_string(),
+            Box::new(|data, sent, events| match T::decode(data) {
+                Some(event) if sent => events.sender::<T>().send(event).is_ok(),
+                Some(event) => {
+                    events.add(event);
+                    true
+                }
+                None => false,
+            }),
+        );
+        self
+    }
+
+    /// The last frame that has events or a frame delta in it.
+    pub fn last_frame(&self) -> Option<u64> {
+        let last_events = self.frames.keys().next_back().copied();
+        let last_delta = self.deltas.keys().next_back().copied();
+        last_events.max(last_delta)
+    }
+
+    /// How long the update after `frame` took, if it was recorded with
+    /// [`EventManager::record_frame_delta`].
+    pub fn frame_delta(&self, frame: u64) -> Option<Duration> {
+        self.deltas.get(&frame).copied()
+    }
+
+    /// Adds the events recorded in `frame` to `events`, in the order they were recorded in, to be
+    /// picked up by the next update. Events that came through an
+    /// [`EventSender`](crate::EventSender) go through one again, so they end up in the same
+    /// buffer they did when recorded.
+    pub fn play_frame(&self, frame: u64, events: &mut EventManager) -> io::Result<()> {
+        for (name, data, sent) in self.frames.get(&frame).into_iter().flatten() {
+            let decoder = self.decoders.get(name).ok_or_else(|| {
+                invalid_data(format!(
+                    "Event type {name} in frame {frame} isn't registered"
+                ))
+            })?;
+            if !decoder(data, *sent, events) {
+                return Err(invalid_data(format!(
+                    "Couldn't decode {name} event in frame {frame} from \"{data}\""
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn escape(data: &str) -> String {
+    data.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+fn unescape(data: &str) -> String {
+    let mut unescaped = String::with_capacity(data.len());
+    let mut chars = data.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
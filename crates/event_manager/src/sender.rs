@@ -0,0 +1,28 @@
+use std::sync::mpsc::{SendError, Sender};
+
+/// Sends events of type `T` to an [`EventManager`](crate::EventManager) from any thread.
+///
+/// Events sent this way show up in the manager on its next
+/// [`update`](crate::EventManager::update), in the order they were sent.
+pub struct EventSender<T> {
+    sender: Sender<T>,
+}
+
+impl<T> EventSender<T> {
+    pub(crate) fn new(sender: Sender<T>) -> Self {
+        Self { sender }
+    }
+
+    /// Fails, giving the event back, if the event manager has been dropped.
+    pub fn send(&self, event: T) -> Result<(), SendError<T>> {
+        self.sender.send(event)
+    }
+}
+
+impl<T> Clone for EventSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            sender: self.sender.clone(),
+        }
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod test {
+    use event_manager::{EventCursor, EventManager, EventReader};
+
+    #[derive(Debug, PartialEq)]
+    struct Hit(u32);
+
+    #[test]
+    fn every_cursor_sees_every_event_once() {
+        let mut events = EventManager::new();
+        let mut first = EventCursor::<Hit>::new();
+        let mut second = EventCursor::<Hit>::new();
+
+        events.add(Hit(1));
+        events.add(Hit(2));
+        assert_eq!(first.read(&events).collect::<Vec<_>>(), [&Hit(1), &Hit(2)]);
+
+        events.add(Hit(3));
+        assert_eq!(first.read(&events).collect::<Vec<_>>(), [&Hit(3)]);
+        assert_eq!(EventReader::new(&events, &mut second).read().count(), 3);
+        assert!(first.is_empty(&events));
+        assert!(second.is_empty(&events));
+    }
+
+    #[test]
+    fn events_are_dropped_after_two_updates() {
+        let mut events = EventManager::new();
+        let mut cursor = EventCursor::<Hit>::new();
+
+        events.add(Hit(1));
+        events.update();
+        events.add(Hit(2));
+        assert_eq!(events.get_event_count::<Hit>(), 2);
+
+        events.update();
+        assert_eq!(cursor.read(&events).collect::<Vec<_>>(), [&Hit(2)]);
+
+        events.update();
+        assert_eq!(events.get_event_count::<Hit>(), 0);
+    }
+
+    #[test]
+    fn get_event_takes_the_oldest_event_for_everyone() {
+        let mut events = EventManager::new();
+        let mut cursor = EventCursor::<Hit>::new();
+
+        events.add(Hit(1));
+        events.update();
+        events.add(Hit(2));
+
+        assert_eq!(events.get_event::<Hit>(), Some(Hit(1)));
+        assert_eq!(cursor.read(&events).collect::<Vec<_>>(), [&Hit(2)]);
+
+        events.return_event(Hit(1));
+        assert_eq!(cursor.read(&events).collect::<Vec<_>>(), [&Hit(1)]);
+        assert_eq!(events.get_event::<Hit>(), Some(Hit(1)));
+    }
+
+    #[test]
+    fn drain_takes_everything_in_order() {
+        let mut events = EventManager::new();
+        events.add(Hit(1));
+        events.update();
+        events.add(Hit(2));
+        events.return_event(Hit(0));
+
+        assert_eq!(
+            events.iter::<Hit>().collect::<Vec<_>>(),
+            [&Hit(0), &Hit(1), &Hit(2)]
+        );
+        assert_eq!(
+            events.drain::<Hit>().collect::<Vec<_>>(),
+            [Hit(0), Hit(1), Hit(2)]
+        );
+        assert_eq!(events.get_event_count::<Hit>(), 0);
+        assert_eq!(events.drain::<Hit>().count(), 0);
+    }
+
+    #[test]
+    fn returned_events_go_before_new_ones() {
+        let mut events = EventManager::new();
+        events.add(Hit(1));
+        events.add(Hit(2));
+
+        let first = events.get_event::<Hit>().unwrap();
+        events.add(Hit(3));
+        events.return_event(first);
+
+        assert_eq!(
+            events.drain::<Hit>().collect::<Vec<_>>(),
+            [Hit(1), Hit(2), Hit(3)]
+        );
+    }
+
+    #[test]
+    fn clear_removes_events() {
+        let mut events = EventManager::new();
+        events.add(Hit(1));
+        events.add(String::from("text"));
+
+        events.clear::<Hit>();
+        assert_eq!(events.get_event_count::<Hit>(), 0);
+        assert_eq!(events.get_event_count::<String>(), 1);
+
+        events.add(Hit(2));
+        events.clear_all();
+        assert_eq!(events.iter::<Hit>().count(), 0);
+        assert_eq!(events.iter::<String>().count(), 0);
+    }
+}
@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use event_manager::EventManager;
+
+    struct Loaded(&'static str);
+
+    #[test]
+    fn handlers_run_on_add_by_priority() {
+        let mut events = EventManager::new();
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        for (priority, name) in [(0, "first"), (10, "urgent"), (0, "second"), (-5, "late")] {
+            let log = log.clone();
+            events.subscribe_with_priority(priority, move |loaded: &Loaded| {
+                log.lock().unwrap().push(format!("{name} {}", loaded.0));
+            });
+        }
+
+        events.add(Loaded("mesh"));
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["urgent mesh", "first mesh", "second mesh", "late mesh"]
+        );
+        assert_eq!(events.get_event_count::<Loaded>(), 1);
+    }
+
+    #[test]
+    fn unsubscribed_handlers_stop_running() {
+        let mut events = EventManager::new();
+        let count = Arc::new(Mutex::new(0));
+
+        let subscription = events.subscribe({
+            let count = count.clone();
+            move |_: &Loaded| *count.lock().unwrap() += 1
+        });
+
+        events.add(Loaded("mesh"));
+        assert!(events.unsubscribe(subscription));
+        assert!(!events.unsubscribe(subscription));
+        events.add(Loaded("texture"));
+        events.return_event(Loaded("mesh"));
+
+        assert_eq!(*count.lock().unwrap(), 1);
+    }
+}
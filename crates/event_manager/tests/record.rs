@@ -0,0 +1,138 @@
+#[cfg(test)]
+mod test {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    use event_manager::{EventManager, RecordableEvent, Recording};
+
+    #[derive(Debug, PartialEq)]
+    struct KeyPressed(String);
+
+    impl RecordableEvent for KeyPressed {
+        const NAME: &'static str = "KeyPressed";
+
+        fn encode(&self) -> String {
+            self.0.clone()
+        }
+
+        fn decode(data: &str) -> Option<Self> {
+            Some(Self(data.to_string()))
+        }
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Scrolled(i32);
+
+    impl RecordableEvent for Scrolled {
+        const NAME: &'static str = "Scrolled";
+
+        fn encode(&self) -> String {
+            self.0.to_string()
+        }
+
+        fn decode(data: &str) -> Option<Self> {
+            data.parse().ok().map(Self)
+        }
+    }
+
+    struct NotRecorded;
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn recorded_events_play_back_in_the_same_frames() {
+        let buffer = Buffer::default();
+        let mut events = EventManager::new();
+        events.start_recording(buffer.clone()).unwrap();
+        events.record::<KeyPressed>();
+        events.record::<Scrolled>();
+
+        events.add(KeyPressed("a".to_string()));
+        events.add(NotRecorded);
+        events.update();
+        events.update();
+        events.add(Scrolled(-3));
+        events.add(KeyPressed("two\nlines \\".to_string()));
+        events.update();
+        events.stop_recording().unwrap();
+        events.add(Scrolled(7));
+
+        let data = buffer.0.lock().unwrap().clone();
+        let mut recording = Recording::read(data.as_slice()).unwrap();
+        recording.register::<KeyPressed>().register::<Scrolled>();
+        assert_eq!(recording.last_frame(), Some(2));
+
+        let mut replayed = EventManager::new();
+        recording.play_frame(0, &mut replayed).unwrap();
+        assert_eq!(
+            replayed.drain::<KeyPressed>().collect::<Vec<_>>(),
+            [KeyPressed("a".to_string())]
+        );
+
+        recording.play_frame(1, &mut replayed).unwrap();
+        assert_eq!(replayed.get_event_count::<KeyPressed>(), 0);
+
+        recording.play_frame(2, &mut replayed).unwrap();
+        assert_eq!(
+            replayed.drain::<Scrolled>().collect::<Vec<_>>(),
+            [Scrolled(-3)]
+        );
+        assert_eq!(
+            replayed.drain::<KeyPressed>().collect::<Vec<_>>(),
+            [KeyPressed("two\nlines \\".to_string())]
+        );
+    }
+
+    #[test]
+    fn sent_events_play_back_into_the_same_buffers() {
+        let buffer = Buffer::default();
+        let mut events = EventManager::new();
+        events.start_recording(buffer.clone()).unwrap();
+        events.record::<Scrolled>();
+        let sender = events.sender::<Scrolled>();
+
+        let mut counts = Vec::new();
+        sender.send(Scrolled(1)).unwrap();
+        for _ in 0..3 {
+            events.update();
+            counts.push(events.get_event_count::<Scrolled>());
+        }
+        events.stop_recording().unwrap();
+        assert_eq!(counts, [1, 1, 0]);
+
+        let data = buffer.0.lock().unwrap().clone();
+        let mut recording = Recording::read(data.as_slice()).unwrap();
+        recording.register::<Scrolled>();
+
+        let mut replayed = EventManager::new();
+        let mut replayed_counts = Vec::new();
+        for frame in 0..3 {
+            recording.play_frame(frame, &mut replayed).unwrap();
+            replayed.update();
+            replayed_counts.push(replayed.get_event_count::<Scrolled>());
+        }
+        assert_eq!(replayed_counts, counts);
+    }
+
+    #[test]
+    fn unregistered_types_fail_to_play() {
+        let recording = Recording::read("spyder-events 1\n0 Scrolled 4\n".as_bytes()).unwrap();
+        let err = recording
+            .play_frame(0, &mut EventManager::new())
+            .unwrap_err();
+        assert!(err.to_string().contains("Scrolled"));
+
+        assert!(Recording::read("0 Scrolled 4\n".as_bytes()).is_err());
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    use event_manager::{EventCursor, EventManager};
+
+    #[derive(Debug, PartialEq)]
+    struct Loaded(u32);
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn event_manager_can_be_shared_between_threads() {
+        assert_send_sync::<EventManager>();
+    }
+
+    #[test]
+    fn events_from_other_threads_arrive_on_update() {
+        let mut events = EventManager::new();
+        let sender = events.sender::<Loaded>();
+
+        let workers: Vec<_> = (0..4)
+            .map(|i| {
+                let sender = sender.clone();
+                thread::spawn(move || sender.send(Loaded(i)).unwrap())
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+        assert_eq!(events.get_event_count::<Loaded>(), 0);
+
+        events.update();
+        let mut received: Vec<_> = EventCursor::<Loaded>::new()
+            .read(&events)
+            .map(|loaded| loaded.0)
+            .collect();
+        received.sort();
+        assert_eq!(received, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn merged_events_reach_subscribers() {
+        let mut events = EventManager::new();
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        events.subscribe({
+            let seen = seen.clone();
+            move |loaded: &Loaded| seen.lock().unwrap().push(loaded.0)
+        });
+
+        let sender = events.sender::<Loaded>();
+        sender.send(Loaded(1)).unwrap();
+        sender.send(Loaded(2)).unwrap();
+        events.update();
+
+        assert_eq!(*seen.lock().unwrap(), [1, 2]);
+        drop(events);
+        assert!(sender.send(Loaded(3)).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use std::ffi::CString;
 
 use app_base::{App, Plugin};
 use ash::vk;
+use ecs::World;
 use event_manager::EventManager;
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 
@@ -11,101 +12,109 @@ use gpu_memory_manager::{
 };
 use logger::internal_log;
 use resource_manager::ResourceManager;
+use scheduler::{condition::on_event, IntoSystemConfig, Stage};
 
 pub struct RasterizationRendererPlugin;
 
 impl Plugin for RasterizationRendererPlugin {
     fn init(&self, app: &mut App) {
-        app.get_scheduler_mut().add_system(render);
+        app.get_scheduler_mut().add_system_to_stage(
+            Stage::Render,
+            render.run_if(on_event::<window::RedrawRequestedEvent>()),
+        );
 
-        let window_handle = app
-            .get_resource_manager_mut()
-            .remove::<window::WindowResource>()
-            .expect("No window handle in resource manager");
+        let spyder = Spyder::new(
+            &app.get_resource_manager()
+                .borrow_non_send::<window::WindowResource>()
+                .window,
+        );
+        app.get_resource_manager_mut().add_non_send(spyder);
+    }
 
-        app.get_resource_manager_mut()
-            .add(Spyder::new(&window_handle.window));
-        app.get_resource_manager_mut().add(window_handle);
+    fn dependencies(&self) -> Vec<&str> {
+        vec![std::any::type_name::<window::WindowPlugin>()]
+    }
+
+    /// Tears the GPU state down while the window is still around.
+    fn cleanup(&self, app: &mut App) {
+        app.get_resource_manager_mut().remove_non_send::<Spyder>();
     }
 }
 
-fn render(events: &mut EventManager, resources: &mut ResourceManager) {
-    if events.get_event::<window::RedrawRequestedEvent>().is_some() {
-        let spyder = resources
-            .get_mut::<Spyder>()
-            .expect("No spyder in resource manager");
-
-        spyder.swap_chain.current_image =
-            (spyder.swap_chain.current_image + 1) % spyder.swap_chain.frames_in_flight;
-        let (image_index, _) = unsafe {
-            spyder
-                .swap_chain
-                .swap_chain_loader
-                .acquire_next_image(
-                    spyder.swap_chain.swap_chain,
-                    u64::MAX,
-                    spyder.swap_chain.image_available[spyder.swap_chain.current_image as usize],
-                    vk::Fence::null(),
-                )
-                .expect("image acquisition trouble")
-        };
+fn render(_world: &mut World, _events: &mut EventManager, resources: &mut ResourceManager) {
+    let spyder = resources
+        .get_non_send_mut::<Spyder>()
+        .expect("No spyder in resource manager");
+
+    spyder.swap_chain.current_image =
+        (spyder.swap_chain.current_image + 1) % spyder.swap_chain.frames_in_flight;
+    let (image_index, _) = unsafe {
+        spyder
+            .swap_chain
+            .swap_chain_loader
+            .acquire_next_image(
+                spyder.swap_chain.swap_chain,
+                u64::MAX,
+                spyder.swap_chain.image_available[spyder.swap_chain.current_image as usize],
+                vk::Fence::null(),
+            )
+            .expect("image acquisition trouble")
+    };
 
-        unsafe {
-            spyder
-                .logical_device
-                .wait_for_fences(
-                    &[spyder.swap_chain.may_begin_drawing
-                        [spyder.swap_chain.current_image as usize]],
-                    true,
-                    u64::MAX,
-                )
-                .expect("fence-waiting");
-            spyder
-                .logical_device
-                .reset_fences(&[
-                    spyder.swap_chain.may_begin_drawing[spyder.swap_chain.current_image as usize]
-                ])
-                .expect("resetting fences");
-        }
+    unsafe {
+        spyder
+            .logical_device
+            .wait_for_fences(
+                &[spyder.swap_chain.may_begin_drawing[spyder.swap_chain.current_image as usize]],
+                true,
+                u64::MAX,
+            )
+            .expect("fence-waiting");
+        spyder
+            .logical_device
+            .reset_fences(&[
+                spyder.swap_chain.may_begin_drawing[spyder.swap_chain.current_image as usize]
+            ])
+            .expect("resetting fences");
+    }
 
-        let semaphores_available =
-            [spyder.swap_chain.image_available[spyder.swap_chain.current_image as usize]];
-        let waiting_stages = [vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
-        let semaphores_finished =
-            [spyder.swap_chain.rendering_finished[spyder.swap_chain.current_image as usize]];
-        let command_buffers = [spyder.command_buffers[image_index as usize]];
-        let submit_info = [vk::SubmitInfo::builder()
-            .wait_semaphores(&semaphores_available)
-            .wait_dst_stage_mask(&waiting_stages)
-            .command_buffers(&command_buffers)
-            .signal_semaphores(&semaphores_finished)
-            .build()];
+    let semaphores_available =
+        [spyder.swap_chain.image_available[spyder.swap_chain.current_image as usize]];
+    let waiting_stages = [vk::PipelineStageFlags::COLOR_ATTACHMENT_OUTPUT];
+    let semaphores_finished =
+        [spyder.swap_chain.rendering_finished[spyder.swap_chain.current_image as usize]];
+    let command_buffers = [spyder.command_buffers[image_index as usize]];
+    let submit_info = [vk::SubmitInfo::builder()
+        .wait_semaphores(&semaphores_available)
+        .wait_dst_stage_mask(&waiting_stages)
+        .command_buffers(&command_buffers)
+        .signal_semaphores(&semaphores_finished)
+        .build()];
 
-        unsafe {
-            spyder
-                .logical_device
-                .queue_submit(
-                    spyder.queues.graphics_queue,
-                    &submit_info,
-                    spyder.swap_chain.may_begin_drawing[spyder.swap_chain.current_image as usize],
-                )
-                .expect("queue submission");
-        };
+    unsafe {
+        spyder
+            .logical_device
+            .queue_submit(
+                spyder.queues.graphics_queue,
+                &submit_info,
+                spyder.swap_chain.may_begin_drawing[spyder.swap_chain.current_image as usize],
+            )
+            .expect("queue submission");
+    };
 
-        let swap_chains = [spyder.swap_chain.swap_chain];
-        let indices = [image_index];
-        let present_info = vk::PresentInfoKHR::builder()
-            .wait_semaphores(&semaphores_finished)
-            .swapchains(&swap_chains)
-            .image_indices(&indices);
-        unsafe {
-            spyder
-                .swap_chain
-                .swap_chain_loader
-                .queue_present(spyder.queues.graphics_queue, &present_info)
-                .expect("queue presentation");
-        };
-    }
+    let swap_chains = [spyder.swap_chain.swap_chain];
+    let indices = [image_index];
+    let present_info = vk::PresentInfoKHR::builder()
+        .wait_semaphores(&semaphores_finished)
+        .swapchains(&swap_chains)
+        .image_indices(&indices);
+    unsafe {
+        spyder
+            .swap_chain
+            .swap_chain_loader
+            .queue_present(spyder.queues.graphics_queue, &present_info)
+            .expect("queue presentation");
+    };
 }
 
 struct Spyder {
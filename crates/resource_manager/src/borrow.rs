@@ -0,0 +1,265 @@
+use std::any::Any;
+use std::cell::UnsafeCell;
+use std::ops::{Deref, DerefMut};
+use std::panic::Location;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::Resource;
+
+const WRITING: usize = usize::MAX;
+
+/// A resource together with the bookkeeping needed to hand out [`Res`] and [`ResMut`] through a
+/// shared reference to the resource manager.
+pub(crate) struct ResourceCell {
+    name: &'static str,
+    value: UnsafeCell<Box<dyn Any>>,
+    /// The number of live `Res`, or `WRITING` while there is a `ResMut`.
+    state: AtomicUsize,
+    /// Where the live guards were created, to name them when a borrow conflicts.
+    borrowers: Mutex<Vec<&'static Location<'static>>>,
+    /// The ticks of the resource manager when the resource was added and last changed.
+    added: u64,
+    changed: AtomicU64,
+}
+
+// Safety: cells made with `new` only hold Send + Sync values and hand them out through the
+// borrow flag. Cells made with `new_non_send` are only reachable through `NonSendResources`,
+// which checks every access is made on the thread that owns them.
+unsafe impl Send for ResourceCell {}
+unsafe impl Sync for ResourceCell {}
+
+impl ResourceCell {
+    pub fn new<T: Resource>(value: T, tick: u64) -> Self {
+        Self::new_non_send(value, tick)
+    }
+
+    pub fn new_non_send<T: Any>(value: T, tick: u64) -> Self {
+        Self {
+            name: std::any::type_name::<T>(),
+            value: UnsafeCell::new(Box::new(value)),
+            state: AtomicUsize::new(0),
+            borrowers: Mutex::default(),
+            added: tick,
+            changed: AtomicU64::new(tick),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn added(&self) -> u64 {
+        self.added
+    }
+
+    pub fn changed(&self) -> u64 {
+        self.changed.load(Ordering::Acquire)
+    }
+
+    /// Marks the resource as changed at `tick`, as there is no telling what happens through the
+    /// returned reference.
+    pub fn get_mut<T: Any>(&mut self, tick: u64) -> &mut T {
+        *self.changed.get_mut() = tick;
+        self.value.get_mut().downcast_mut().unwrap()
+    }
+
+    pub fn into_inner<T: Any>(self) -> T {
+        *self.value.into_inner().downcast().unwrap()
+    }
+
+    #[track_caller]
+    pub fn borrow<T: Any>(&self) -> Res<'_, T> {
+        let mut state = self.state.load(Ordering::Acquire);
+        loop {
+            if state == WRITING {
+                self.conflict("immutably");
+            }
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(current) => state = current,
+            }
+        }
+
+        // Safety: the state now counts this borrow, so no ResMut can be created until it is
+        // released.
+        let value = unsafe { (*self.value.get()).downcast_ref().unwrap() };
+        Res {
+            value,
+            borrow: Borrow::new(self),
+            last_run: 0,
+        }
+    }
+
+    /// Changing the resource through the guard marks it as changed at `tick`.
+    #[track_caller]
+    pub fn borrow_mut<T: Any>(&self, tick: u64) -> ResMut<'_, T> {
+        if self
+            .state
+            .compare_exchange(0, WRITING, Ordering::Acquire, Ordering::Acquire)
+            .is_err()
+        {
+            self.conflict("mutably");
+        }
+
+        // Safety: the state is WRITING, so no other guard can be created until it is released.
+        let value = unsafe { (*self.value.get()).downcast_mut().unwrap() };
+        ResMut {
+            value,
+            borrow: Borrow::new(self),
+            last_run: 0,
+            tick,
+        }
+    }
+
+    #[track_caller]
+    fn conflict(&self, how: &str) -> ! {
+        let borrowers: Vec<_> = self
+            .borrowers
+            .lock()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let held = if self.state.load(Ordering::Acquire) == WRITING {
+            "mutably"
+        } else {
+            "immutably"
+        };
+        panic!(
+            "Can't borrow resource {} {how} at {}, it is already borrowed {held} at {}",
+            self.name,
+            Location::caller(),
+            borrowers.join(", ")
+        );
+    }
+}
+
+/// Releases a borrow of a [`ResourceCell`] when dropped.
+struct Borrow<'a> {
+    cell: &'a ResourceCell,
+    location: &'static Location<'static>,
+}
+
+impl<'a> Borrow<'a> {
+    #[track_caller]
+    fn new(cell: &'a ResourceCell) -> Self {
+        let location = Location::caller();
+        cell.borrowers.lock().unwrap().push(location);
+        Self { cell, location }
+    }
+}
+
+impl Drop for Borrow<'_> {
+    fn drop(&mut self) {
+        let mut borrowers = self.cell.borrowers.lock().unwrap();
+        if let Some(index) = borrowers.iter().position(|other| *other == self.location) {
+            borrowers.swap_remove(index);
+        }
+        drop(borrowers);
+
+        if self.cell.state.load(Ordering::Acquire) == WRITING {
+            self.cell.state.store(0, Ordering::Release);
+        } else {
+            self.cell.state.fetch_sub(1, Ordering::Release);
+        }
+    }
+}
+
+/// Shared access to a resource. Any number of these can exist at once, but no [`ResMut`] of the
+/// same resource.
+pub struct Res<'a, T> {
+    value: &'a T,
+    borrow: Borrow<'a>,
+    last_run: u64,
+}
+
+impl<T> Res<'_, T> {
+    /// Where this borrow was made.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.borrow.location
+    }
+
+    /// Makes [`Res::is_added`] and [`Res::is_changed`] only count what happened after the tick
+    /// `last_run`, instead of since the resource was added. The scheduler passes the tick its
+    /// system last ran at.
+    pub fn since(mut self, last_run: u64) -> Self {
+        self.last_run = last_run;
+        self
+    }
+
+    pub fn is_added(&self) -> bool {
+        self.borrow.cell.added() > self.last_run
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.borrow.cell.changed() > self.last_run
+    }
+
+    /// The tick the resource was last changed at.
+    pub fn last_changed(&self) -> u64 {
+        self.borrow.cell.changed()
+    }
+}
+
+impl<T> Deref for Res<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+/// Exclusive access to a resource. Dereferencing it mutably marks the resource as changed.
+pub struct ResMut<'a, T> {
+    value: &'a mut T,
+    borrow: Borrow<'a>,
+    last_run: u64,
+    tick: u64,
+}
+
+impl<T> ResMut<'_, T> {
+    /// Where this borrow was made.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.borrow.location
+    }
+
+    /// See [`Res::since`].
+    pub fn since(mut self, last_run: u64) -> Self {
+        self.last_run = last_run;
+        self
+    }
+
+    pub fn is_added(&self) -> bool {
+        self.borrow.cell.added() > self.last_run
+    }
+
+    pub fn is_changed(&self) -> bool {
+        self.borrow.cell.changed() > self.last_run
+    }
+
+    /// The tick the resource was last changed at.
+    pub fn last_changed(&self) -> u64 {
+        self.borrow.cell.changed()
+    }
+}
+
+impl<T> Deref for ResMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value
+    }
+}
+
+impl<T> DerefMut for ResMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.borrow.cell.changed.store(self.tick, Ordering::Release);
+        self.value
+    }
+}
@@ -0,0 +1,48 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+type Hook = Box<dyn FnMut(&dyn Any) + Send + Sync>;
+
+#[derive(Default)]
+struct ResourceHooks {
+    on_insert: Vec<Hook>,
+    on_remove: Vec<Hook>,
+}
+
+/// Callbacks run when resources of a type are added to or removed from the resource manager.
+#[derive(Default)]
+pub(crate) struct Hooks {
+    hooks: HashMap<TypeId, ResourceHooks>,
+}
+
+impl Hooks {
+    pub fn add_on_insert<T: Any>(&mut self, hook: impl FnMut(&T) + Send + Sync + 'static) {
+        let hooks = self.hooks.entry(TypeId::of::<T>()).or_default();
+        hooks.on_insert.push(erase(hook));
+    }
+
+    pub fn add_on_remove<T: Any>(&mut self, hook: impl FnMut(&T) + Send + Sync + 'static) {
+        let hooks = self.hooks.entry(TypeId::of::<T>()).or_default();
+        hooks.on_remove.push(erase(hook));
+    }
+
+    pub fn inserted<T: Any>(&mut self, resource: &T) {
+        if let Some(hooks) = self.hooks.get_mut(&TypeId::of::<T>()) {
+            for hook in &mut hooks.on_insert {
+                hook(resource);
+            }
+        }
+    }
+
+    pub fn removed<T: Any>(&mut self, resource: &T) {
+        if let Some(hooks) = self.hooks.get_mut(&TypeId::of::<T>()) {
+            for hook in &mut hooks.on_remove {
+                hook(resource);
+            }
+        }
+    }
+}
+
+fn erase<T: Any>(mut hook: impl FnMut(&T) + Send + Sync + 'static) -> Hook {
+    Box::new(move |resource| hook(resource.downcast_ref().unwrap()))
+}
@@ -1,16 +1,66 @@
+pub mod borrow;
+mod hooks;
+mod non_send;
+
 use std::any::Any;
 use std::collections::HashMap;
 
+use error::{engine_error, EngineError, EngineResult};
+
+use crate::borrow::ResourceCell;
+use crate::hooks::Hooks;
+use crate::non_send::NonSendResources;
+
+pub use crate::borrow::{Res, ResMut};
+
 pub mod prelude {
-    pub use super::ResourceManager;
+    pub use super::{Res, ResMut, Resource, ResourceManager};
 }
 
+/// Resources that systems running on other threads are allowed to access. Anything else has to be
+/// added with [`ResourceManager::add_non_send`].
+pub trait Resource: Send + Sync + 'static {}
+
+impl<T: Send + Sync + 'static> Resource for T {}
+
 #[derive(Eq, PartialEq, Hash)]
 struct ResourceID(std::any::TypeId);
 
-#[derive(Default)]
+/// Resources by type.
+///
+/// Through a shared reference, resources are accessed with [`ResourceManager::borrow`] and
+/// [`ResourceManager::borrow_mut`], which are checked at runtime like a `RefCell`, so several
+/// resources can be held mutably at once. Borrowing a resource that is already borrowed
+/// mutably, or mutably borrowing one that is borrowed at all, panics naming both places.
+///
+/// Resources that aren't [`Resource`]s are kept separately through the `_non_send` methods, which
+/// panic when called from any thread but the one that created the resource manager. That keeps
+/// the resource manager itself `Send + Sync`.
+///
+/// Every resource remembers the tick it was added and last changed at, see
+/// [`ResourceManager::is_changed`]. The scheduler advances the tick before every batch of systems
+/// it runs, and changes made through `&mut ResourceManager` advance it themselves.
+///
+/// The `try_` methods return a `MissingResource` error naming the type when it doesn't exist,
+/// and the `_unchecked` ones panic with the same message, which also lists the resources that
+/// do exist.
 pub struct ResourceManager {
-    resources: HashMap<ResourceID, Box<dyn Any>>,
+    resources: HashMap<ResourceID, ResourceCell>,
+    non_send: NonSendResources,
+    hooks: Hooks,
+    tick: u64,
+}
+
+impl Default for ResourceManager {
+    fn default() -> Self {
+        Self {
+            resources: HashMap::new(),
+            non_send: NonSendResources::default(),
+            hooks: Hooks::default(),
+            // Zero is left for "never", so everything counts as changed since then.
+            tick: 1,
+        }
+    }
 }
 
 impl ResourceManager {
@@ -18,51 +68,251 @@ impl ResourceManager {
         Default::default()
     }
 
-    pub fn add<T: Any>(&mut self, resource: T) {
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Starts a new tick and returns it.
+    pub fn increment_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    /// Replaces the resource if it already exists, running its on-remove hooks first.
+    pub fn add<T: Resource>(&mut self, resource: T) {
         let id = self.get_resource_id::<T>();
+        let tick = self.increment_tick();
 
-        self.resources.insert(id, Box::new(resource));
+        if let Some(old) = self.resources.remove(&id) {
+            self.hooks.removed(&old.into_inner::<T>());
+        }
+        self.hooks.inserted(&resource);
+        self.resources.insert(id, ResourceCell::new(resource, tick));
     }
 
-    pub fn get<T: Any>(&self) -> Option<&T> {
+    /// Whether the resource was added after the tick `since`. False if it doesn't exist.
+    pub fn is_added<T: Resource>(&self, since: u64) -> bool {
         let id = self.get_resource_id::<T>();
         self.resources
             .get(&id)
-            .map(|resource| resource.as_ref().downcast_ref().unwrap())
+            .is_some_and(|resource| resource.added() > since)
     }
 
-    pub fn get_mut<T: Any>(&mut self) -> Option<&mut T> {
+    /// Whether the resource was added or changed after the tick `since`. False if it doesn't
+    /// exist.
+    pub fn is_changed<T: Resource>(&self, since: u64) -> bool {
         let id = self.get_resource_id::<T>();
         self.resources
-            .get_mut(&id)
-            .map(|resource| resource.as_mut().downcast_mut().unwrap())
+            .get(&id)
+            .is_some_and(|resource| resource.changed() > since)
+    }
+
+    /// Calls `hook` with every resource of type `T` added from now on, before it is added.
+    pub fn on_insert<T: Any>(&mut self, hook: impl FnMut(&T) + Send + Sync + 'static) {
+        self.hooks.add_on_insert(hook);
     }
 
-    pub fn get_unchecked<T: Any>(&self) -> &T {
+    /// Calls `hook` with every resource of type `T` that is removed or replaced from now on.
+    pub fn on_remove<T: Any>(&mut self, hook: impl FnMut(&T) + Send + Sync + 'static) {
+        self.hooks.add_on_remove(hook);
+    }
+
+    pub fn contains<T: Resource>(&self) -> bool {
         let id = self.get_resource_id::<T>();
-        self.resources.get(&id).unwrap().downcast_ref().unwrap()
+        self.resources.contains_key(&id)
     }
 
-    pub fn get_mut_unchecked<T: Any>(&mut self) -> &mut T {
+    /// Like [`ResourceManager::borrow`], but returns `None` if `T` doesn't exist.
+    // Not `Option::map`, the closure would be reported as the borrower instead of the caller.
+    #[allow(clippy::manual_map)]
+    #[track_caller]
+    pub fn get<T: Resource>(&self) -> Option<Res<'_, T>> {
         let id = self.get_resource_id::<T>();
-        self.resources.get_mut(&id).unwrap().downcast_mut().unwrap()
+        match self.resources.get(&id) {
+            Some(resource) => Some(resource.borrow()),
+            None => None,
+        }
     }
 
-    pub fn remove<T: Any>(&mut self) -> Option<T> {
+    pub fn get_mut<T: Resource>(&mut self) -> Option<&mut T> {
         let id = self.get_resource_id::<T>();
+        let tick = self.increment_tick();
         self.resources
-            .remove(&id)
-            .map(|resource| *resource.downcast().unwrap())
+            .get_mut(&id)
+            .map(|resource| resource.get_mut(tick))
+    }
+
+    #[track_caller]
+    pub fn try_get<T: Resource>(&self) -> EngineResult<Res<'_, T>> {
+        let id = self.get_resource_id::<T>();
+        match self.resources.get(&id) {
+            Some(resource) => Ok(resource.borrow()),
+            None => self.missing::<T>().as_result(),
+        }
+    }
+
+    pub fn try_get_mut<T: Resource>(&mut self) -> EngineResult<&mut T> {
+        if !self.contains::<T>() {
+            return self.missing::<T>().as_result();
+        }
+        Ok(self.get_mut_unchecked())
     }
 
-    pub fn remove_unchecked<T: Any>(&mut self) -> T {
+    #[track_caller]
+    pub fn get_unchecked<T: Resource>(&self) -> Res<'_, T> {
+        self.borrow()
+    }
+
+    #[track_caller]
+    pub fn get_mut_unchecked<T: Resource>(&mut self) -> &mut T {
         let id = self.get_resource_id::<T>();
-        *self
+        if !self.resources.contains_key(&id) {
+            self.panic_missing::<T>();
+        }
+        let tick = self.increment_tick();
+        self.resources.get_mut(&id).unwrap().get_mut(tick)
+    }
+
+    /// Shared access to the resource `T`, which panics if it doesn't exist or is borrowed
+    /// mutably.
+    #[track_caller]
+    pub fn borrow<T: Resource>(&self) -> Res<'_, T> {
+        self.cell::<T>().borrow()
+    }
+
+    /// Exclusive access to the resource `T`, which panics if it doesn't exist or is borrowed.
+    #[track_caller]
+    pub fn borrow_mut<T: Resource>(&self) -> ResMut<'_, T> {
+        self.cell::<T>().borrow_mut(self.tick)
+    }
+
+    pub fn remove<T: Resource>(&mut self) -> Option<T> {
+        let id = self.get_resource_id::<T>();
+        let resource = self.resources.remove(&id)?.into_inner();
+        self.hooks.removed(&resource);
+        Some(resource)
+    }
+
+    pub fn try_remove<T: Resource>(&mut self) -> EngineResult<T> {
+        match self.remove() {
+            Some(resource) => Ok(resource),
+            None => self.missing::<T>().as_result(),
+        }
+    }
+
+    #[track_caller]
+    pub fn remove_unchecked<T: Resource>(&mut self) -> T {
+        match self.remove() {
+            Some(resource) => resource,
+            None => self.panic_missing::<T>(),
+        }
+    }
+
+    #[track_caller]
+    pub fn add_non_send<T: Any>(&mut self, resource: T) {
+        let tick = self.increment_tick();
+        if let Some(old) = self.non_send.remove::<T>() {
+            self.hooks.removed(&old.into_inner::<T>());
+        }
+        self.hooks.inserted(&resource);
+        self.non_send.insert(resource, tick);
+    }
+
+    #[track_caller]
+    pub fn contains_non_send<T: Any>(&self) -> bool {
+        self.non_send.get::<T>().is_some()
+    }
+
+    #[allow(clippy::manual_map)]
+    #[track_caller]
+    pub fn get_non_send<T: Any>(&self) -> Option<Res<'_, T>> {
+        match self.non_send.get::<T>() {
+            Some(resource) => Some(resource.borrow()),
+            None => None,
+        }
+    }
+
+    #[track_caller]
+    pub fn get_non_send_mut<T: Any>(&mut self) -> Option<&mut T> {
+        let tick = self.increment_tick();
+        self.non_send
+            .get_mut::<T>()
+            .map(|resource| resource.get_mut(tick))
+    }
+
+    #[track_caller]
+    pub fn try_get_non_send<T: Any>(&self) -> EngineResult<Res<'_, T>> {
+        match self.non_send.get::<T>() {
+            Some(resource) => Ok(resource.borrow()),
+            None => self.missing::<T>().as_result(),
+        }
+    }
+
+    #[track_caller]
+    pub fn borrow_non_send<T: Any>(&self) -> Res<'_, T> {
+        match self.non_send.get::<T>() {
+            Some(resource) => resource.borrow(),
+            None => self.panic_missing::<T>(),
+        }
+    }
+
+    #[track_caller]
+    pub fn borrow_non_send_mut<T: Any>(&self) -> ResMut<'_, T> {
+        match self.non_send.get::<T>() {
+            Some(resource) => resource.borrow_mut(self.tick),
+            None => self.panic_missing::<T>(),
+        }
+    }
+
+    #[track_caller]
+    pub fn remove_non_send<T: Any>(&mut self) -> Option<T> {
+        let resource = self.non_send.remove::<T>()?.into_inner();
+        self.hooks.removed(&resource);
+        Some(resource)
+    }
+
+    #[track_caller]
+    pub fn try_remove_non_send<T: Any>(&mut self) -> EngineResult<T> {
+        match self.remove_non_send() {
+            Some(resource) => Ok(resource),
+            None => self.missing::<T>().as_result(),
+        }
+    }
+
+    /// The type names of every resource, including the non-send ones, sorted.
+    pub fn names(&self) -> Vec<&'static str> {
+        let mut names: Vec<_> = self
             .resources
-            .remove(&id)
-            .expect("Tried removing resource from the resource manager")
-            .downcast::<T>()
-            .unwrap()
+            .values()
+            .map(ResourceCell::name)
+            .chain(self.non_send.names())
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[track_caller]
+    fn cell<T: Resource>(&self) -> &ResourceCell {
+        let id = self.get_resource_id::<T>();
+        match self.resources.get(&id) {
+            Some(resource) => resource,
+            None => self.panic_missing::<T>(),
+        }
+    }
+
+    fn missing<T>(&self) -> EngineError {
+        let names = self.names();
+        engine_error!(
+            "MissingResource",
+            "Resource {} does not exist, the resource manager has: [{}]",
+            std::any::type_name::<T>(),
+            names.join(", ")
+        )
+    }
+
+    #[track_caller]
+    fn panic_missing<T>(&self) -> ! {
+        panic!("{}", self.missing::<T>().get_msg())
     }
 
     fn get_resource_id<T: Any>(&self) -> ResourceID {
@@ -0,0 +1,82 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::thread::{self, ThreadId};
+
+use crate::borrow::ResourceCell;
+
+/// Resources that have to stay on the thread that created the resource manager, like windows
+/// and event loops. Every access checks it is made from that thread.
+pub(crate) struct NonSendResources {
+    thread: ThreadId,
+    resources: HashMap<TypeId, ResourceCell>,
+}
+
+impl Default for NonSendResources {
+    fn default() -> Self {
+        Self {
+            thread: thread::current().id(),
+            resources: HashMap::new(),
+        }
+    }
+}
+
+impl NonSendResources {
+    #[track_caller]
+    pub fn insert<T: Any>(&mut self, resource: T, tick: u64) {
+        self.check_thread::<T>();
+        self.resources.insert(
+            TypeId::of::<T>(),
+            ResourceCell::new_non_send(resource, tick),
+        );
+    }
+
+    #[track_caller]
+    pub fn get<T: Any>(&self) -> Option<&ResourceCell> {
+        self.check_thread::<T>();
+        self.resources.get(&TypeId::of::<T>())
+    }
+
+    #[track_caller]
+    pub fn get_mut<T: Any>(&mut self) -> Option<&mut ResourceCell> {
+        self.check_thread::<T>();
+        self.resources.get_mut(&TypeId::of::<T>())
+    }
+
+    #[track_caller]
+    pub fn remove<T: Any>(&mut self) -> Option<ResourceCell> {
+        self.check_thread::<T>();
+        self.resources.remove(&TypeId::of::<T>())
+    }
+
+    /// Only the names, so it is fine to call from any thread.
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.resources.values().map(ResourceCell::name)
+    }
+
+    #[track_caller]
+    fn check_thread<T: Any>(&self) {
+        if thread::current().id() != self.thread {
+            panic!(
+                "Non-send resource {} accessed from thread {:?}, it can only be used on the thread that created the resource manager ({:?})",
+                std::any::type_name::<T>(),
+                thread::current().id(),
+                self.thread
+            );
+        }
+    }
+}
+
+impl Drop for NonSendResources {
+    fn drop(&mut self) {
+        if self.resources.is_empty() || thread::current().id() == self.thread {
+            return;
+        }
+
+        // Dropping them here would run their destructors on the wrong thread, leaking them is
+        // the only safe option left.
+        std::mem::forget(std::mem::take(&mut self.resources));
+        if !thread::panicking() {
+            panic!("A resource manager holding non-send resources was dropped on another thread");
+        }
+    }
+}
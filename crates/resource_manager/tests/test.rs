@@ -28,9 +28,134 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(expected = "Resource test::test::Foo does not exist")]
     fn unchecked_methods() {
         let rs = ResourceManager::new();
         rs.get_unchecked::<Foo>();
     }
+
+    #[test]
+    fn missing_resources_are_errors() {
+        let mut rs = get_resource_manager();
+        assert_eq!(rs.try_remove::<Foo>().ok(), Some(Foo));
+        rs.try_get_mut::<Bar>().unwrap().data += 1;
+        assert_eq!(rs.try_get::<Bar>().unwrap().data, 43);
+
+        let err = rs.try_get::<Foo>().err().unwrap();
+        assert_eq!(err.get_id(), "MissingResource");
+        assert_eq!(
+            err.get_msg(),
+            "Resource test::test::Foo does not exist, the resource manager has: [test::test::Bar]"
+        );
+        assert!(rs.try_get_non_send::<Foo>().is_err());
+
+        let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rs.remove_unchecked::<Foo>();
+        }))
+        .unwrap_err();
+        let msg = err.downcast_ref::<String>().unwrap();
+        assert!(msg.contains("[test::test::Bar]"), "{msg}");
+    }
+
+    #[test]
+    fn borrowing_several_resources_mutably() {
+        let rs = get_resource_manager();
+
+        let mut bar = rs.borrow_mut::<Bar>();
+        let foo = rs.borrow::<Foo>();
+        let also_foo = rs.get::<Foo>().unwrap();
+        bar.data += 1;
+        assert_eq!(*foo, *also_foo);
+        drop(bar);
+
+        assert_eq!(rs.borrow::<Bar>().data, 43);
+    }
+
+    #[test]
+    fn conflicting_borrows_name_both_borrowers() {
+        let rs = get_resource_manager();
+
+        let foo = rs.borrow::<Foo>();
+        let location = foo.location().to_string();
+        let err = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            rs.borrow_mut::<Foo>();
+        }))
+        .unwrap_err();
+
+        let msg = err.downcast_ref::<String>().unwrap();
+        assert!(msg.contains("Foo"), "{msg}");
+        assert!(msg.contains(&location), "{msg}");
+        assert!(msg.contains("tests/test.rs"), "{msg}");
+        drop(foo);
+
+        rs.borrow_mut::<Foo>();
+    }
+
+    #[test]
+    fn non_send_resources_stay_on_their_thread() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ResourceManager>();
+
+        let mut rs = get_resource_manager();
+        rs.add_non_send(std::rc::Rc::new(7));
+        assert_eq!(**rs.borrow_non_send::<std::rc::Rc<i32>>(), 7);
+        assert!(!rs.contains_non_send::<Foo>());
+
+        std::thread::scope(|scope| {
+            let rs = &rs;
+            assert_eq!(scope.spawn(|| rs.borrow::<Bar>().data).join().unwrap(), 42);
+            assert!(scope
+                .spawn(|| rs.contains_non_send::<std::rc::Rc<i32>>())
+                .join()
+                .is_err());
+        });
+
+        assert_eq!(
+            rs.remove_non_send::<std::rc::Rc<i32>>().as_deref(),
+            Some(&7)
+        );
+    }
+
+    #[test]
+    fn tracking_changes_since_a_tick() {
+        let mut rs = get_resource_manager();
+        let start = rs.tick();
+        assert!(!rs.is_changed::<Bar>(start));
+
+        rs.borrow_mut::<Bar>().data += 0;
+        assert!(!rs.is_changed::<Bar>(start));
+
+        let tick = rs.increment_tick();
+        assert!(!rs.borrow_mut::<Bar>().since(start).is_changed());
+        rs.borrow_mut::<Bar>().data += 1;
+        assert!(rs.is_changed::<Bar>(start));
+        assert!(!rs.is_changed::<Bar>(tick));
+        assert!(!rs.is_added::<Bar>(start));
+        assert!(rs.borrow::<Bar>().since(start).is_changed());
+
+        rs.get_mut::<Foo>();
+        assert!(rs.is_changed::<Foo>(tick));
+        rs.add(Foo);
+        assert!(rs.is_added::<Foo>(tick));
+    }
+
+    #[test]
+    fn hooks_see_inserted_and_removed_resources() {
+        use std::sync::{Arc, Mutex};
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut rs = ResourceManager::new();
+        let inserted = log.clone();
+        rs.on_insert(move |bar: &Bar| inserted.lock().unwrap().push(format!("+{}", bar.data)));
+        let removed = log.clone();
+        rs.on_remove(move |bar: &Bar| removed.lock().unwrap().push(format!("-{}", bar.data)));
+
+        rs.add(Foo);
+        rs.add(Bar { data: 1 });
+        rs.add(Bar { data: 2 });
+        rs.remove::<Bar>();
+        rs.remove::<Bar>();
+
+        assert_eq!(*log.lock().unwrap(), ["+1", "-1", "+2", "-2"]);
+    }
 }
@@ -0,0 +1,121 @@
+use std::any::TypeId;
+
+use ecs::ComponentAccess;
+
+#[derive(Clone, Copy, Debug)]
+struct ResourceId {
+    id: TypeId,
+    name: &'static str,
+}
+
+impl ResourceId {
+    fn of<T: 'static>() -> Self {
+        Self {
+            id: TypeId::of::<T>(),
+            name: std::any::type_name::<T>(),
+        }
+    }
+}
+
+/// Everything a system touches while it runs.
+///
+/// Exclusive systems get the world, events and resources as plain `&mut` references, so they
+/// conflict with everything. Systems touching non-send resources have to run on the thread that
+/// calls the scheduler.
+#[derive(Clone, Debug, Default)]
+pub struct Access {
+    components: ComponentAccess,
+    resource_reads: Vec<ResourceId>,
+    resource_writes: Vec<ResourceId>,
+    exclusive: bool,
+    main_thread: bool,
+}
+
+impl Access {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn exclusive() -> Self {
+        Self {
+            exclusive: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_exclusive(&self) -> bool {
+        self.exclusive
+    }
+
+    pub fn is_main_thread_only(&self) -> bool {
+        self.exclusive || self.main_thread
+    }
+
+    pub fn set_main_thread_only(&mut self) {
+        self.main_thread = true;
+    }
+
+    pub fn add_resource_read<T: 'static>(&mut self) {
+        self.resource_reads.push(ResourceId::of::<T>());
+    }
+
+    pub fn add_resource_write<T: 'static>(&mut self) {
+        self.resource_writes.push(ResourceId::of::<T>());
+    }
+
+    pub fn add_components(&mut self, access: &ComponentAccess) {
+        self.components.extend(access);
+    }
+
+    /// Adds everything `other` touches.
+    pub fn extend(&mut self, other: &Access) {
+        self.components.extend(&other.components);
+        self.resource_reads.extend_from_slice(&other.resource_reads);
+        self.resource_writes
+            .extend_from_slice(&other.resource_writes);
+        self.exclusive |= other.exclusive;
+        self.main_thread |= other.main_thread;
+    }
+
+    pub fn components(&self) -> &ComponentAccess {
+        &self.components
+    }
+
+    /// Names of everything that is written more than once, or both written and read.
+    pub fn self_conflicts(&self) -> Vec<&'static str> {
+        let mut conflicts = self.components.self_conflicts();
+        for (i, write) in self.resource_writes.iter().enumerate() {
+            let aliased = self.resource_writes[i + 1..]
+                .iter()
+                .chain(&self.resource_reads)
+                .any(|other| other.id == write.id);
+            if aliased && !conflicts.contains(&write.name) {
+                conflicts.push(write.name);
+            }
+        }
+        conflicts
+    }
+
+    /// Names of everything both access where at least one of them writes. Exclusive access
+    /// conflicts with everything.
+    pub fn conflicts_with(&self, other: &Access) -> Vec<&'static str> {
+        if self.exclusive || other.exclusive {
+            return vec!["exclusive access"];
+        }
+
+        let mut conflicts = self.components.conflicts_with(&other.components);
+        let mut check = |writes: &[ResourceId], accessed: &[ResourceId]| {
+            for write in writes {
+                if accessed.iter().any(|other| other.id == write.id)
+                    && !conflicts.contains(&write.name)
+                {
+                    conflicts.push(write.name);
+                }
+            }
+        };
+        check(&self.resource_writes, &other.resource_writes);
+        check(&self.resource_writes, &other.resource_reads);
+        check(&other.resource_writes, &self.resource_reads);
+        conflicts
+    }
+}
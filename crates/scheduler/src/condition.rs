@@ -0,0 +1,126 @@
+use event_manager::{Event, EventReader};
+use resource_manager::{Res, Resource};
+use time::Time;
+
+use crate::access::Access;
+use crate::param::{ReadOnlySystemParam, SystemContext};
+use crate::system::{FunctionMarker, FunctionSystem, SystemParamFunction};
+
+/// Decides whether a system runs this frame. Conditions are checked right before the system
+/// would run, and what they read counts towards the system's access, so systems writing it are
+/// never in the same batch.
+pub trait Condition: Send + 'static {
+    fn access(&self) -> &Access;
+
+    /// # Safety
+    /// Nothing running at the same time may write to what [`Condition::access`] reads.
+    unsafe fn evaluate(&mut self, ctx: SystemContext) -> bool;
+}
+
+/// Conversion into a [`Condition`]. Implemented for functions returning `bool` whose arguments
+/// are all [`ReadOnlySystemParam`]s, e.g. `|paused: Res<Paused>| !paused.0`.
+pub trait IntoCondition<Marker> {
+    type Condition: Condition;
+
+    fn into_condition(self) -> Self::Condition;
+}
+
+pub struct FunctionCondition<Marker, F: SystemParamFunction<Marker>> {
+    system: FunctionSystem<Marker, F>,
+}
+
+impl<Marker: 'static, F: SystemParamFunction<Marker, Out = bool>> Condition
+    for FunctionCondition<Marker, F>
+{
+    fn access(&self) -> &Access {
+        &self.system.meta.access
+    }
+
+    unsafe fn evaluate(&mut self, ctx: SystemContext) -> bool {
+        self.system.call(ctx)
+    }
+}
+
+impl<Marker: 'static, F> IntoCondition<(FunctionMarker, Marker)> for F
+where
+    F: SystemParamFunction<Marker, Out = bool>,
+    F::Param: ReadOnlySystemParam,
+{
+    type Condition = FunctionCondition<Marker, F>;
+
+    fn into_condition(self) -> Self::Condition {
+        FunctionCondition {
+            system: FunctionSystem::new(self),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct ConditionMarker;
+
+impl<C: Condition> IntoCondition<ConditionMarker> for C {
+    type Condition = C;
+
+    fn into_condition(self) -> Self::Condition {
+        self
+    }
+}
+
+pub fn resource_exists<T: Resource>() -> impl Condition {
+    (|resource: Option<Res<T>>| resource.is_some()).into_condition()
+}
+
+/// True when the resource `T` exists and equals `state`, e.g. `in_state(AppState::Playing)`.
+pub fn in_state<T: Resource + PartialEq>(state: T) -> impl Condition {
+    (move |value: Option<Res<T>>| value.is_some_and(|value| *value == state)).into_condition()
+}
+
+/// True when the resource `T` has been added or changed since the last check.
+pub fn resource_changed<T: Resource>() -> impl Condition {
+    (|resource: Option<Res<T>>| resource.is_some_and(|resource| resource.is_changed()))
+        .into_condition()
+}
+
+/// True when events of type `T` have been sent since the last check.
+pub fn on_event<T: Event>() -> impl Condition {
+    (|mut events: EventReader<T>| {
+        let unread = !events.is_empty();
+        events.clear();
+        unread
+    })
+    .into_condition()
+}
+
+/// True on the first frame and then on every `n`th one after it, going by
+/// [`Time::frame_count`], so it doesn't matter how often it's checked. Never true without a
+/// [`Time`] resource.
+///
+/// # Panics
+/// If `n` is 0.
+pub fn every_n_frames(n: u32) -> impl Condition {
+    assert!(n > 0, "every_n_frames needs a frame count above 0");
+    (move |time: Option<Res<Time>>| {
+        time.is_some_and(|time| time.frame_count().saturating_sub(1) % u64::from(n) == 0)
+    })
+    .into_condition()
+}
+
+pub fn not<Marker>(condition: impl IntoCondition<Marker>) -> impl Condition {
+    Not {
+        condition: condition.into_condition(),
+    }
+}
+
+struct Not<C> {
+    condition: C,
+}
+
+impl<C: Condition> Condition for Not<C> {
+    fn access(&self) -> &Access {
+        self.condition.access()
+    }
+
+    unsafe fn evaluate(&mut self, ctx: SystemContext) -> bool {
+        !self.condition.evaluate(ctx)
+    }
+}
@@ -0,0 +1,99 @@
+use ecs::World;
+use event_manager::EventManager;
+use resource_manager::ResourceManager;
+
+use crate::access::Access;
+use crate::condition::{Condition, IntoCondition};
+use crate::param::SystemContext;
+use crate::system::{IntoSystem, System};
+
+/// A system together with its labels and ordering constraints.
+pub struct SystemConfig {
+    pub(crate) system: Box<dyn System>,
+    pub(crate) labels: Vec<&'static str>,
+    pub(crate) before: Vec<&'static str>,
+    pub(crate) after: Vec<&'static str>,
+    pub(crate) conditions: Vec<Box<dyn Condition>>,
+    /// The system's access together with what its conditions read, which is what ordering and
+    /// batching have to look at.
+    pub(crate) access: Access,
+}
+
+impl SystemConfig {
+    pub(crate) fn should_run(
+        &mut self,
+        world: &mut World,
+        events: &mut EventManager,
+        resources: &mut ResourceManager,
+    ) -> bool {
+        let ctx = SystemContext::new(world, events, resources);
+        // Conditions are checked with nothing else running. Every one of them is, even after one
+        // fails, so the ones keeping track of what they've seen stay up to date.
+        let mut run = true;
+        for condition in &mut self.conditions {
+            run &= unsafe { condition.evaluate(ctx) };
+        }
+        run
+    }
+}
+
+/// Lets ordering constraints be chained onto anything that can become a system, e.g.
+/// `physics.label("physics").after("input").before("transform")`.
+pub trait IntoSystemConfig<Marker>: Sized {
+    fn into_config(self) -> SystemConfig;
+
+    /// Names the system so others can order themselves relative to it. Several systems can
+    /// share a label.
+    fn label(self, label: &'static str) -> SystemConfig {
+        let mut config = self.into_config();
+        config.labels.push(label);
+        config
+    }
+
+    /// Runs the system before every system with the label in the same stage.
+    fn before(self, label: &'static str) -> SystemConfig {
+        let mut config = self.into_config();
+        config.before.push(label);
+        config
+    }
+
+    /// Runs the system after every system with the label in the same stage.
+    fn after(self, label: &'static str) -> SystemConfig {
+        let mut config = self.into_config();
+        config.after.push(label);
+        config
+    }
+
+    /// Only runs the system on frames where the condition holds. Several conditions all have
+    /// to hold, and all of them are checked every time.
+    fn run_if<M>(self, condition: impl IntoCondition<M>) -> SystemConfig {
+        let mut config = self.into_config();
+        let condition = condition.into_condition();
+        config.access.extend(condition.access());
+        config.conditions.push(Box::new(condition));
+        config
+    }
+}
+
+impl<Marker, S: IntoSystem<Marker>> IntoSystemConfig<Marker> for S {
+    fn into_config(self) -> SystemConfig {
+        let system = self.into_system();
+        SystemConfig {
+            access: system.access().clone(),
+            system: Box::new(system),
+            labels: Vec::new(),
+            before: Vec::new(),
+            after: Vec::new(),
+            conditions: Vec::new(),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub struct ConfigMarker;
+
+impl IntoSystemConfig<ConfigMarker> for SystemConfig {
+    fn into_config(self) -> SystemConfig {
+        self
+    }
+}
@@ -0,0 +1,202 @@
+use std::any::Any;
+use std::num::NonZeroUsize;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+
+use ecs::World;
+use event_manager::EventManager;
+use resource_manager::ResourceManager;
+
+use crate::param::SystemContext;
+use crate::system::System;
+
+/// How the systems of a stage get run.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExecutorKind {
+    /// One after another on the calling thread, in the sorted order.
+    SingleThreaded,
+    /// Systems whose access doesn't conflict run at the same time on worker threads. Exclusive
+    /// systems still run alone on the calling thread, and systems using non-send resources run
+    /// on the calling thread alongside the workers.
+    #[default]
+    MultiThreaded,
+}
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Worker threads kept for the lifetime of the scheduler, so running a batch only hands its
+/// systems over instead of spawning threads every frame. The threads are started on first use.
+pub(crate) struct ThreadPool {
+    threads: NonZeroUsize,
+    workers: OnceLock<Workers>,
+}
+
+struct Workers {
+    jobs: Sender<Job>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl Default for ThreadPool {
+    fn default() -> Self {
+        Self::new(thread::available_parallelism().unwrap_or(NonZeroUsize::MIN))
+    }
+}
+
+impl ThreadPool {
+    pub fn new(threads: NonZeroUsize) -> Self {
+        Self {
+            threads,
+            workers: OnceLock::new(),
+        }
+    }
+
+    fn workers(&self) -> &Workers {
+        self.workers.get_or_init(|| {
+            let (sender, receiver) = mpsc::channel();
+            let receiver = Arc::new(Mutex::new(receiver));
+            let handles = (0..self.threads.get())
+                .map(|index| {
+                    let receiver = receiver.clone();
+                    thread::Builder::new()
+                        .name(format!("scheduler worker {index}"))
+                        .spawn(move || work(&receiver))
+                        .expect("Failed to spawn a scheduler worker thread")
+                })
+                .collect();
+            Workers {
+                jobs: sender,
+                handles,
+            }
+        })
+    }
+
+    /// Runs `jobs` on the workers and `inline` on the calling thread, returning once all of them
+    /// are done. A panic in any of them is resumed on the calling thread after that.
+    fn run<'a>(&self, jobs: Vec<Box<dyn FnOnce() + Send + 'a>>, inline: impl FnOnce()) {
+        let latch = Arc::new(Latch {
+            remaining: Mutex::new(0),
+            done: Condvar::new(),
+            panic: Mutex::new(None),
+        });
+
+        {
+            // Waits for the jobs even if `inline` panics, as they borrow from the caller.
+            let _wait = WaitGuard(&latch);
+            let sender = &self.workers().jobs;
+            for job in jobs {
+                // SAFETY: `_wait` blocks until every job has run, so nothing the job borrows for
+                // 'a goes away while it's still running.
+                let job: Box<dyn FnOnce() + Send + 'static> = unsafe { std::mem::transmute(job) };
+                let latch = latch.clone();
+                *latch.remaining.lock().unwrap() += 1;
+                let job: Job = Box::new(move || {
+                    let result = panic::catch_unwind(AssertUnwindSafe(job));
+                    latch.finish(result.err());
+                });
+                // The workers only stop once the pool is dropped, but rather run the job here
+                // than wait on it forever.
+                if let Err(mpsc::SendError(job)) = sender.send(job) {
+                    job();
+                }
+            }
+            inline();
+        }
+
+        let panic = latch.panic.lock().unwrap().take();
+        if let Some(payload) = panic {
+            panic::resume_unwind(payload);
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        if let Some(workers) = self.workers.take() {
+            // Closing the channel stops the workers once they are idle.
+            drop(workers.jobs);
+            for handle in workers.handles {
+                let _ = handle.join();
+            }
+        }
+    }
+}
+
+fn work(jobs: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = jobs.lock().unwrap().recv();
+        match job {
+            Ok(job) => job(),
+            Err(_) => return,
+        }
+    }
+}
+
+struct Latch {
+    remaining: Mutex<usize>,
+    done: Condvar,
+    panic: Mutex<Option<Box<dyn Any + Send>>>,
+}
+
+impl Latch {
+    fn finish(&self, panic: Option<Box<dyn Any + Send>>) {
+        if let Some(panic) = panic {
+            self.panic.lock().unwrap().get_or_insert(panic);
+        }
+        *self.remaining.lock().unwrap() -= 1;
+        self.done.notify_all();
+    }
+}
+
+struct WaitGuard<'a>(&'a Latch);
+
+impl Drop for WaitGuard<'_> {
+    fn drop(&mut self) {
+        let remaining = self.0.remaining.lock().unwrap();
+        drop(
+            self.0
+                .done
+                .wait_while(remaining, |remaining| *remaining > 0),
+        );
+    }
+}
+
+/// Runs systems that have been checked to not conflict with each other at the same time on the
+/// pool's workers, then applies their buffered changes in order.
+pub(crate) fn run_parallel(
+    pool: &ThreadPool,
+    systems: &mut [&mut Box<dyn System>],
+    world: &mut World,
+    event_manager: &mut EventManager,
+    resource_manager: &mut ResourceManager,
+) {
+    match systems {
+        [] => return,
+        [system] => return system.run(world, event_manager, resource_manager),
+        _ => (),
+    }
+
+    let ctx = SystemContext::new(world, event_manager, resource_manager);
+    let (main_thread, workers): (Vec<_>, Vec<_>) = systems
+        .iter_mut()
+        .partition(|system| system.access().is_main_thread_only());
+    let mut workers = workers.into_iter();
+    // With nothing of its own to run, the calling thread takes one of the workers' systems
+    // instead of just waiting.
+    let inline = main_thread.is_empty().then(|| workers.next()).flatten();
+    let jobs = workers
+        .map(|system| {
+            Box::new(move || unsafe { system.run_unsafe(ctx) }) as Box<dyn FnOnce() + Send>
+        })
+        .collect();
+    pool.run(jobs, || {
+        for system in main_thread.into_iter().chain(inline) {
+            unsafe { system.run_unsafe(ctx) };
+        }
+    });
+
+    for system in systems {
+        system.apply(event_manager);
+    }
+}
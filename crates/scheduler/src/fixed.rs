@@ -0,0 +1,87 @@
+use std::time::Duration;
+
+/// Drives [`Stage::FixedUpdate`](crate::Stage::FixedUpdate). Lives in the resource manager,
+/// add one before the first update to change the rate.
+///
+/// The virtual frame delta of [`Time`](time::Time) is accumulated and spent in steps of exactly
+/// `step`, so the fixed stage runs zero or more times per frame. Whatever is left over is exposed
+/// as [`FixedTime::alpha`] for interpolating between the last two steps when rendering.
+#[derive(Clone, Debug)]
+pub struct FixedTime {
+    step: Duration,
+    accumulator: Duration,
+    max_accumulation: Duration,
+    steps: u64,
+}
+
+impl Default for FixedTime {
+    fn default() -> Self {
+        Self::from_hz(60.0)
+    }
+}
+
+impl FixedTime {
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "The fixed timestep can't be zero");
+        Self {
+            step,
+            accumulator: Duration::ZERO,
+            max_accumulation: Duration::from_millis(250),
+            steps: 0,
+        }
+    }
+
+    pub fn from_hz(hz: f64) -> Self {
+        assert!(
+            hz.is_finite() && hz > 0.0,
+            "The fixed timestep rate has to be a positive number, got {hz}"
+        );
+        Self::new(Duration::from_secs_f64(1.0 / hz))
+    }
+
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    pub fn set_step(&mut self, step: Duration) {
+        assert!(!step.is_zero(), "The fixed timestep can't be zero");
+        self.step = step;
+    }
+
+    /// Caps how much time can pile up, so a long frame doesn't cause a burst of steps that
+    /// makes the next frame even longer. The cap never goes below one step, or no step could
+    /// ever be taken.
+    pub fn set_max_accumulation(&mut self, max_accumulation: Duration) {
+        self.max_accumulation = max_accumulation;
+    }
+
+    pub fn accumulated(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// How many steps have run in total.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// How far between the last step and the next one the current frame is, from 0 to 1.
+    pub fn alpha(&self) -> f32 {
+        self.accumulator.as_secs_f32() / self.step.as_secs_f32()
+    }
+
+    pub fn accumulate(&mut self, delta: Duration) {
+        let cap = self.max_accumulation.max(self.step);
+        self.accumulator = (self.accumulator + delta).min(cap);
+    }
+
+    /// Takes one step worth of time out of the accumulator, if there is enough.
+    pub fn expend(&mut self) -> bool {
+        if self.accumulator < self.step {
+            return false;
+        }
+
+        self.accumulator -= self.step;
+        self.steps += 1;
+        true
+    }
+}
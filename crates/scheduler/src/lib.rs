@@ -1,11 +1,43 @@
+pub mod access;
+pub mod condition;
+pub mod config;
+pub mod executor;
+pub mod fixed;
+pub mod param;
+pub mod stage;
+pub mod system;
+
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
+
+use ecs::World;
+use error::EngineResult;
 use event_manager::EventManager;
 use resource_manager::ResourceManager;
+use time::Time;
+
+pub use crate::access::Access;
+pub use crate::condition::{Condition, IntoCondition};
+pub use crate::config::{IntoSystemConfig, SystemConfig};
+pub use crate::executor::ExecutorKind;
+pub use crate::fixed::FixedTime;
+pub use crate::param::{EventWriter, NonSend, NonSendMut, ReadOnlySystemParam, SystemParam};
+pub use crate::stage::{AmbiguityDetection, Stage};
 
-type System = dyn FnMut(&mut EventManager, &mut ResourceManager);
+use crate::executor::ThreadPool;
+use crate::stage::SystemStage;
+pub use crate::system::{IntoSystem, System};
+
+pub use ecs::{Query, With, Without};
+pub use event_manager::EventReader;
+pub use resource_manager::{Res, ResMut};
 
 #[derive(Default)]
 pub struct Scheduler {
-    systems: Vec<Box<System>>,
+    stages: HashMap<Stage, SystemStage>,
+    ambiguity_detection: AmbiguityDetection,
+    executor: ExecutorKind,
+    pool: ThreadPool,
+    startup_done: bool,
 }
 
 impl Scheduler {
@@ -13,20 +45,123 @@ impl Scheduler {
         Self::default()
     }
 
-    pub fn add_system<F>(&mut self, system: F)
-    where
-        F: FnMut(&mut EventManager, &mut ResourceManager) + 'static,
-    {
-        self.systems.push(Box::new(system));
+    /// Adds a system to [`Stage::Update`].
+    ///
+    /// Takes either a function taking [`SystemParam`]s, e.g.
+    /// `fn movement(time: Res<Time>, mut query: Query<(&mut Pos, &Vel)>)`, or one taking
+    /// `(&mut World, &mut EventManager, &mut ResourceManager)` for exclusive access. Either can
+    /// be given labels and ordering constraints through [`IntoSystemConfig`].
+    pub fn add_system<M>(&mut self, system: impl IntoSystemConfig<M>) {
+        self.add_system_to_stage(Stage::Update, system);
+    }
+
+    /// Adds a system that runs once before the first frame.
+    pub fn add_startup_system<M>(&mut self, system: impl IntoSystemConfig<M>) {
+        self.add_system_to_stage(Stage::Startup, system);
+    }
+
+    pub fn add_system_to_stage<M>(&mut self, stage: Stage, system: impl IntoSystemConfig<M>) {
+        self.stages
+            .entry(stage)
+            .or_default()
+            .add(system.into_config());
+    }
+
+    pub fn set_ambiguity_detection(&mut self, ambiguity_detection: AmbiguityDetection) {
+        self.ambiguity_detection = ambiguity_detection;
     }
 
+    pub fn set_executor(&mut self, executor: ExecutorKind) {
+        self.executor = executor;
+    }
+
+    /// Sets how many worker threads [`ExecutorKind::MultiThreaded`] keeps around, one per
+    /// available core by default.
+    pub fn set_worker_threads(&mut self, threads: NonZeroUsize) {
+        self.pool = ThreadPool::new(threads);
+    }
+
+    /// Orders the systems of every stage that changed since the last build.
+    ///
+    /// Happens automatically on the first update after adding systems, call it directly to
+    /// get cycles and ambiguities reported as errors instead of panics.
+    pub fn build(&mut self) -> EngineResult<()> {
+        for (stage, systems) in &mut self.stages {
+            if systems.is_dirty() {
+                systems.build(*stage, self.ambiguity_detection)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs every stage once, running [`Stage::Startup`] first if this is the first update.
+    ///
+    /// # Panics
+    /// If the systems can't be ordered, see [`Scheduler::build`].
     pub fn update(
         &mut self,
+        world: &mut World,
+        event_manager: &mut EventManager,
+        resource_manager: &mut ResourceManager,
+    ) {
+        if let Err(error) = self.build() {
+            panic!("{error}");
+        }
+
+        if !self.startup_done {
+            self.startup_done = true;
+            self.run_stage(Stage::Startup, world, event_manager, resource_manager);
+        }
+
+        // Without a Time resource nothing advances it, so no fixed steps are taken either.
+        let delta = resource_manager
+            .get::<Time>()
+            .map(|time| time.delta())
+            .unwrap_or_default();
+
+        for stage in Stage::FRAME {
+            if stage == Stage::FixedUpdate {
+                self.run_fixed_update(delta, world, event_manager, resource_manager);
+            } else {
+                self.run_stage(stage, world, event_manager, resource_manager);
+            }
+        }
+    }
+
+    fn run_fixed_update(
+        &mut self,
+        delta: Duration,
+        world: &mut World,
+        event_manager: &mut EventManager,
+        resource_manager: &mut ResourceManager,
+    ) {
+        if !resource_manager.contains::<FixedTime>() {
+            resource_manager.add(FixedTime::default());
+        }
+        resource_manager
+            .get_mut_unchecked::<FixedTime>()
+            .accumulate(delta);
+
+        while resource_manager.get_mut_unchecked::<FixedTime>().expend() {
+            self.run_stage(Stage::FixedUpdate, world, event_manager, resource_manager);
+        }
+    }
+
+    fn run_stage(
+        &mut self,
+        stage: Stage,
+        world: &mut World,
         event_manager: &mut EventManager,
         resource_manager: &mut ResourceManager,
     ) {
-        for system in &mut self.systems {
-            system(event_manager, resource_manager);
+        if let Some(systems) = self.stages.get_mut(&stage) {
+            systems.run(
+                self.executor,
+                &self.pool,
+                world,
+                event_manager,
+                resource_manager,
+            );
         }
     }
 }
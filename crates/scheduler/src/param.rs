@@ -0,0 +1,419 @@
+use std::{
+    any::Any,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use ecs::{
+    query::{QueryData, QueryFilter, ReadOnlyQueryData},
+    Query, World,
+};
+use event_manager::{Event, EventCursor, EventManager, EventReader};
+use resource_manager::{Res, ResMut, Resource, ResourceManager};
+
+use crate::access::Access;
+
+/// Name and access of a system, filled in by its parameters.
+#[derive(Debug, Default)]
+pub struct SystemMeta {
+    pub name: String,
+    pub access: Access,
+    /// The resource manager's tick the last time the system ran, 0 if it never did.
+    pub last_run: u64,
+}
+
+/// Raw pointers to what systems run against, so parameters can split them up.
+#[derive(Clone, Copy)]
+pub struct SystemContext<'w> {
+    world: *mut World,
+    events: *mut EventManager,
+    resources: *mut ResourceManager,
+    _marker: PhantomData<&'w mut ()>,
+}
+
+// Safety: the pointers are only dereferenced by systems whose access has been checked not to
+// conflict with anything else running at the same time, and every parameter that hands out
+// data requires it to be Send + Sync, except for the non-send ones, whose systems only run on
+// the thread that owns the data.
+unsafe impl Send for SystemContext<'_> {}
+unsafe impl Sync for SystemContext<'_> {}
+
+impl<'w> SystemContext<'w> {
+    pub fn new(
+        world: &'w mut World,
+        events: &'w mut EventManager,
+        resources: &'w mut ResourceManager,
+    ) -> Self {
+        Self {
+            world,
+            events,
+            resources,
+            _marker: PhantomData,
+        }
+    }
+
+    /// # Safety
+    /// Nothing may write to the components read through this reference.
+    pub unsafe fn world(self) -> &'w World {
+        &*self.world
+    }
+
+    /// # Safety
+    /// Nothing else may access the world while the returned reference lives.
+    pub unsafe fn world_mut(self) -> &'w mut World {
+        &mut *self.world
+    }
+
+    /// # Safety
+    /// Nothing may write to the events read through this reference.
+    pub unsafe fn events(self) -> &'w EventManager {
+        &*self.events
+    }
+
+    /// # Safety
+    /// Nothing else may access the events while the returned reference lives.
+    pub unsafe fn events_mut(self) -> &'w mut EventManager {
+        &mut *self.events
+    }
+
+    /// # Safety
+    /// Nothing may access the resource manager mutably while the returned reference lives.
+    /// Resources themselves are borrow checked at runtime.
+    pub unsafe fn resources(self) -> &'w ResourceManager {
+        &*self.resources
+    }
+
+    /// # Safety
+    /// Nothing else may access the resources that are touched through this reference.
+    pub unsafe fn resources_mut(self) -> &'w mut ResourceManager {
+        &mut *self.resources
+    }
+}
+
+/// Something a system function can take as an argument.
+///
+/// # Safety
+/// `init_state` has to register everything `get_param` touches in the system's access.
+pub unsafe trait SystemParam {
+    type State: Send + 'static;
+    type Item<'w, 's>;
+
+    fn init_state(meta: &mut SystemMeta) -> Self::State;
+
+    /// # Safety
+    /// The access registered in `init_state` must not be violated for `'w`.
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's>;
+
+    /// Called after the system ran, with nothing else running.
+    fn apply(_state: &mut Self::State, _events: &mut EventManager) {}
+}
+
+/// Marks parameters that never write to anything, which are the ones run conditions can take.
+///
+/// # Safety
+/// Only implement this when `init_state` registers no writes.
+pub unsafe trait ReadOnlySystemParam: SystemParam {}
+
+pub type SystemParamItem<'w, 's, P> = <P as SystemParam>::Item<'w, 's>;
+
+fn missing_resource<T>(meta: &SystemMeta, resources: &ResourceManager) -> ! {
+    panic!(
+        "Resource {} requested by system {} does not exist, the resource manager has: [{}]",
+        std::any::type_name::<T>(),
+        meta.name,
+        resources.names().join(", ")
+    )
+}
+
+/// The system panics if the resource doesn't exist, use `Option<Res<T>>` if it might not.
+unsafe impl<T: Resource> SystemParam for Res<'_, T> {
+    type State = ();
+    type Item<'w, 's> = Res<'w, T>;
+
+    fn init_state(meta: &mut SystemMeta) -> Self::State {
+        meta.access.add_resource_read::<T>();
+    }
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        let resources = ctx.resources();
+        match resources.get::<T>() {
+            Some(value) => value.since(meta.last_run),
+            None => missing_resource::<T>(meta, resources),
+        }
+    }
+}
+
+unsafe impl<T: Resource> SystemParam for Option<Res<'_, T>> {
+    type State = ();
+    type Item<'w, 's> = Option<Res<'w, T>>;
+
+    fn init_state(meta: &mut SystemMeta) -> Self::State {
+        meta.access.add_resource_read::<T>();
+    }
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        ctx.resources()
+            .get::<T>()
+            .map(|value| value.since(meta.last_run))
+    }
+}
+
+unsafe impl<T: Resource> ReadOnlySystemParam for Res<'_, T> {}
+
+unsafe impl<T: Resource> ReadOnlySystemParam for Option<Res<'_, T>> {}
+
+/// The system panics if the resource doesn't exist, use `Option<ResMut<T>>` if it might not.
+unsafe impl<T: Resource> SystemParam for ResMut<'_, T> {
+    type State = ();
+    type Item<'w, 's> = ResMut<'w, T>;
+
+    fn init_state(meta: &mut SystemMeta) -> Self::State {
+        meta.access.add_resource_write::<T>();
+    }
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        let resources = ctx.resources();
+        if !resources.contains::<T>() {
+            missing_resource::<T>(meta, resources);
+        }
+        resources.borrow_mut::<T>().since(meta.last_run)
+    }
+}
+
+unsafe impl<T: Resource> SystemParam for Option<ResMut<'_, T>> {
+    type State = ();
+    type Item<'w, 's> = Option<ResMut<'w, T>>;
+
+    fn init_state(meta: &mut SystemMeta) -> Self::State {
+        meta.access.add_resource_write::<T>();
+    }
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        let resources = ctx.resources();
+        resources
+            .contains::<T>()
+            .then(|| resources.borrow_mut::<T>().since(meta.last_run))
+    }
+}
+
+/// Shared access to the non-send resource `T`. Systems using it run on the thread that calls the
+/// scheduler.
+pub struct NonSend<'w, T: 'static>(Res<'w, T>);
+
+impl<T> Deref for NonSend<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+/// Exclusive access to the non-send resource `T`. Systems using it run on the thread that calls
+/// the scheduler.
+pub struct NonSendMut<'w, T: 'static>(ResMut<'w, T>);
+
+impl<T> Deref for NonSendMut<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for NonSendMut<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+unsafe impl<T: Any> SystemParam for NonSend<'_, T> {
+    type State = ();
+    type Item<'w, 's> = NonSend<'w, T>;
+
+    fn init_state(meta: &mut SystemMeta) -> Self::State {
+        meta.access.add_resource_read::<T>();
+        meta.access.set_main_thread_only();
+    }
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        let resources = ctx.resources();
+        match resources.get_non_send::<T>() {
+            Some(value) => NonSend(value),
+            None => missing_resource::<T>(meta, resources),
+        }
+    }
+}
+
+unsafe impl<T: Any> ReadOnlySystemParam for NonSend<'_, T> {}
+
+unsafe impl<T: Any> SystemParam for NonSendMut<'_, T> {
+    type State = ();
+    type Item<'w, 's> = NonSendMut<'w, T>;
+
+    fn init_state(meta: &mut SystemMeta) -> Self::State {
+        meta.access.add_resource_write::<T>();
+        meta.access.set_main_thread_only();
+    }
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        let resources = ctx.resources();
+        if !resources.contains_non_send::<T>() {
+            missing_resource::<T>(meta, resources);
+        }
+        NonSendMut(resources.borrow_non_send_mut::<T>())
+    }
+}
+
+unsafe impl<Q: QueryData + 'static, F: QueryFilter + 'static> SystemParam for Query<'_, Q, F> {
+    type State = ();
+    type Item<'w, 's> = Query<'w, Q, F>;
+
+    fn init_state(meta: &mut SystemMeta) -> Self::State {
+        meta.access.add_components(&World::query_access::<Q, F>());
+    }
+
+    unsafe fn get_param<'w, 's>(
+        _state: &'s mut Self::State,
+        _meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        ctx.world().query_unchecked::<Q, F>()
+    }
+}
+
+unsafe impl<Q: ReadOnlyQueryData + 'static, F: QueryFilter + 'static> ReadOnlySystemParam
+    for Query<'_, Q, F>
+{
+}
+
+/// Sends events of type `T`.
+///
+/// Events are buffered and only show up in the [`EventManager`] once the system has finished.
+pub struct EventWriter<'s, T> {
+    events: &'s mut Vec<T>,
+}
+
+impl<T> EventWriter<'_, T> {
+    pub fn send(&mut self, event: T) {
+        self.events.push(event);
+    }
+
+    pub fn send_batch(&mut self, events: impl IntoIterator<Item = T>) {
+        self.events.extend(events);
+    }
+}
+
+unsafe impl<T: Event> SystemParam for EventWriter<'_, T> {
+    type State = Vec<T>;
+    type Item<'w, 's> = EventWriter<'s, T>;
+
+    fn init_state(_meta: &mut SystemMeta) -> Self::State {
+        Vec::new()
+    }
+
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        _meta: &SystemMeta,
+        _ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        EventWriter { events: state }
+    }
+
+    fn apply(state: &mut Self::State, events: &mut EventManager) {
+        for event in state.drain(..) {
+            events.add(event);
+        }
+    }
+}
+
+/// Reads events of type `T`. Every system has its own cursor, so each one sees every event once.
+unsafe impl<T: Event> SystemParam for EventReader<'_, '_, T> {
+    type State = EventCursor<T>;
+    type Item<'w, 's> = EventReader<'w, 's, T>;
+
+    fn init_state(_meta: &mut SystemMeta) -> Self::State {
+        EventCursor::new()
+    }
+
+    unsafe fn get_param<'w, 's>(
+        state: &'s mut Self::State,
+        _meta: &SystemMeta,
+        ctx: SystemContext<'w>,
+    ) -> Self::Item<'w, 's> {
+        EventReader::new(ctx.events(), state)
+    }
+}
+
+unsafe impl<T: Event> ReadOnlySystemParam for EventReader<'_, '_, T> {}
+
+macro_rules! impl_param_tuple {
+    ($($param:ident),*) => {
+        #[allow(non_snake_case, unused_variables, clippy::unused_unit)]
+        unsafe impl<$($param: SystemParam),*> SystemParam for ($($param,)*) {
+            type State = ($($param::State,)*);
+            type Item<'w, 's> = ($($param::Item<'w, 's>,)*);
+
+            fn init_state(meta: &mut SystemMeta) -> Self::State {
+                ($($param::init_state(meta),)*)
+            }
+
+            unsafe fn get_param<'w, 's>(
+                state: &'s mut Self::State,
+                meta: &SystemMeta,
+                ctx: SystemContext<'w>,
+            ) -> Self::Item<'w, 's> {
+                let ($($param,)*) = state;
+                ($($param::get_param($param, meta, ctx),)*)
+            }
+
+            fn apply(state: &mut Self::State, events: &mut EventManager) {
+                let ($($param,)*) = state;
+                $($param::apply($param, events);)*
+            }
+        }
+
+        unsafe impl<$($param: ReadOnlySystemParam),*> ReadOnlySystemParam for ($($param,)*) {}
+    };
+}
+
+impl_param_tuple!();
+impl_param_tuple!(P0);
+impl_param_tuple!(P0, P1);
+impl_param_tuple!(P0, P1, P2);
+impl_param_tuple!(P0, P1, P2, P3);
+impl_param_tuple!(P0, P1, P2, P3, P4);
+impl_param_tuple!(P0, P1, P2, P3, P4, P5);
+impl_param_tuple!(P0, P1, P2, P3, P4, P5, P6);
+impl_param_tuple!(P0, P1, P2, P3, P4, P5, P6, P7);
+impl_param_tuple!(P0, P1, P2, P3, P4, P5, P6, P7, P8);
+impl_param_tuple!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9);
+impl_param_tuple!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10);
+impl_param_tuple!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11);
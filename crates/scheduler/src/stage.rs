@@ -0,0 +1,242 @@
+use ecs::World;
+use error::{engine_error, EngineResult};
+use event_manager::EventManager;
+use logger::warn;
+use resource_manager::ResourceManager;
+
+use crate::config::SystemConfig;
+use crate::executor::{self, ExecutorKind, ThreadPool};
+
+/// Labelled groups of systems. Every frame runs the stages in the order they are declared here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Stage {
+    /// Runs once, before the first frame.
+    Startup,
+    /// Input handling and anything else that has to see the frame before gameplay does.
+    PreUpdate,
+    /// Runs zero or more times per frame at the rate set by [`FixedTime`](crate::FixedTime).
+    FixedUpdate,
+    Update,
+    PostUpdate,
+    Render,
+    Last,
+}
+
+impl Stage {
+    /// The stages that run every frame, in order.
+    pub const FRAME: [Stage; 6] = [
+        Stage::PreUpdate,
+        Stage::FixedUpdate,
+        Stage::Update,
+        Stage::PostUpdate,
+        Stage::Render,
+        Stage::Last,
+    ];
+}
+
+/// What to do about two systems in a stage that conflict in their access but have no ordering
+/// between them, so their relative order only depends on the order they were added in.
+///
+/// Denied by default, so building the schedule fails. Warn and Ignore are opt-outs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AmbiguityDetection {
+    Ignore,
+    Warn,
+    #[default]
+    Deny,
+}
+
+#[derive(Default)]
+pub(crate) struct SystemStage {
+    systems: Vec<SystemConfig>,
+    order: Vec<usize>,
+    batches: Vec<Vec<usize>>,
+    dirty: bool,
+}
+
+impl SystemStage {
+    pub fn add(&mut self, config: SystemConfig) {
+        self.systems.push(config);
+        self.dirty = true;
+    }
+
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Sorts the systems so every `before`/`after` constraint holds, keeping the insertion order
+    /// where there are none.
+    pub fn build(&mut self, stage: Stage, ambiguity: AmbiguityDetection) -> EngineResult<()> {
+        let count = self.systems.len();
+        let mut successors = vec![Vec::new(); count];
+
+        for (index, config) in self.systems.iter().enumerate() {
+            for label in &config.after {
+                for other in self.labelled(stage, index, label) {
+                    successors[other].push(index);
+                }
+            }
+            for label in &config.before {
+                for other in self.labelled(stage, index, label) {
+                    successors[index].push(other);
+                }
+            }
+        }
+
+        let order = match utils::topological_sort(&successors) {
+            Ok(order) => order,
+            Err(cycle) => {
+                let cycle: Vec<_> = cycle
+                    .into_iter()
+                    .map(|index| self.systems[index].system.name())
+                    .collect();
+                return engine_error!(
+                    "SystemOrderCycle",
+                    "Ordering constraints in stage {stage:?} form a cycle between: {}",
+                    cycle.join(", ")
+                )
+                .as_result();
+            }
+        };
+
+        if ambiguity != AmbiguityDetection::Ignore {
+            let ambiguities = self.ambiguities(&successors);
+            if !ambiguities.is_empty() {
+                let msg = format!(
+                    "Systems in stage {stage:?} conflict without an ordering between them:\n{}",
+                    ambiguities.join("\n")
+                );
+                if ambiguity == AmbiguityDetection::Deny {
+                    return engine_error!("AmbiguousSystemOrder", "{msg}").as_result();
+                }
+                warn!("{msg}");
+            }
+        }
+
+        self.batches = self.batches(&order, &successors);
+        self.order = order;
+        self.dirty = false;
+        Ok(())
+    }
+
+    pub fn run(
+        &mut self,
+        executor: ExecutorKind,
+        pool: &ThreadPool,
+        world: &mut World,
+        event_manager: &mut EventManager,
+        resource_manager: &mut ResourceManager,
+    ) {
+        match executor {
+            ExecutorKind::SingleThreaded => {
+                for &index in &self.order {
+                    let config = &mut self.systems[index];
+                    if config.should_run(world, event_manager, resource_manager) {
+                        resource_manager.increment_tick();
+                        config.system.run(world, event_manager, resource_manager);
+                    }
+                }
+            }
+            ExecutorKind::MultiThreaded => {
+                for batch in &self.batches {
+                    let mut systems: Vec<_> = self
+                        .systems
+                        .iter_mut()
+                        .enumerate()
+                        .filter(|(index, _)| batch.contains(index))
+                        .filter_map(|(_, config)| {
+                            config
+                                .should_run(world, event_manager, resource_manager)
+                                .then_some(&mut config.system)
+                        })
+                        .collect();
+                    // Every system in the batch shares the tick, none of them can see the
+                    // changes of the others anyway.
+                    resource_manager.increment_tick();
+                    executor::run_parallel(
+                        pool,
+                        &mut systems,
+                        world,
+                        event_manager,
+                        resource_manager,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Splits the sorted systems into runs that can go in parallel. A system starts a new batch
+    /// when it conflicts with, or has to run after, something in the current one, so running
+    /// the batches one after another keeps the sorted order wherever it matters.
+    fn batches(&self, order: &[usize], successors: &[Vec<usize>]) -> Vec<Vec<usize>> {
+        let mut batches: Vec<Vec<usize>> = Vec::new();
+        for &index in order {
+            let access = &self.systems[index].access;
+            let fits = batches.last().is_some_and(|batch| {
+                !access.is_exclusive()
+                    && batch.iter().all(|&other| {
+                        !successors[other].contains(&index)
+                            && access
+                                .conflicts_with(&self.systems[other].access)
+                                .is_empty()
+                    })
+            });
+
+            match batches.last_mut() {
+                Some(batch) if fits => batch.push(index),
+                _ => batches.push(vec![index]),
+            }
+        }
+        batches
+    }
+
+    fn labelled(&self, stage: Stage, index: usize, label: &str) -> Vec<usize> {
+        let labelled: Vec<_> = (0..self.systems.len())
+            .filter(|other| *other != index && self.systems[*other].labels.contains(&label))
+            .collect();
+        if labelled.is_empty() {
+            warn!(
+                "System {} is ordered relative to label \"{label}\", which no other system in stage {stage:?} has",
+                self.systems[index].system.name()
+            );
+        }
+        labelled
+    }
+
+    fn ambiguities(&self, successors: &[Vec<usize>]) -> Vec<String> {
+        let count = self.systems.len();
+        let reachable: Vec<Vec<bool>> = (0..count)
+            .map(|start| {
+                let mut seen = vec![false; count];
+                let mut stack = successors[start].clone();
+                while let Some(index) = stack.pop() {
+                    if !seen[index] {
+                        seen[index] = true;
+                        stack.extend_from_slice(&successors[index]);
+                    }
+                }
+                seen
+            })
+            .collect();
+
+        let mut ambiguities = Vec::new();
+        for (a, first) in self.systems.iter().enumerate() {
+            for (b, second) in self.systems.iter().enumerate().skip(a + 1) {
+                if reachable[a][b] || reachable[b][a] {
+                    continue;
+                }
+
+                let conflicts = first.access.conflicts_with(&second.access);
+                if !conflicts.is_empty() {
+                    ambiguities.push(format!(
+                        "    {} and {} both access {}",
+                        first.system.name(),
+                        second.system.name(),
+                        conflicts.join(", ")
+                    ));
+                }
+            }
+        }
+        ambiguities
+    }
+}
@@ -0,0 +1,215 @@
+use std::marker::PhantomData;
+
+use ecs::World;
+use event_manager::EventManager;
+use resource_manager::ResourceManager;
+
+use crate::access::Access;
+use crate::param::{SystemContext, SystemMeta, SystemParam, SystemParamItem};
+
+pub trait System: Send + 'static {
+    fn name(&self) -> &str;
+
+    fn access(&self) -> &Access;
+
+    /// # Safety
+    /// Nothing running at the same time may conflict with [`System::access`].
+    unsafe fn run_unsafe(&mut self, ctx: SystemContext);
+
+    /// Flushes anything the system buffered while running, e.g. sent events.
+    fn apply(&mut self, events: &mut EventManager);
+
+    fn run(
+        &mut self,
+        world: &mut World,
+        events: &mut EventManager,
+        resources: &mut ResourceManager,
+    ) {
+        unsafe { self.run_unsafe(SystemContext::new(world, events, resources)) };
+        self.apply(events);
+    }
+}
+
+/// Conversion into a [`System`]. Implemented for functions whose arguments are all
+/// [`SystemParam`]s, and for functions taking `(&mut World, &mut EventManager, &mut ResourceManager)`.
+///
+/// The marker only exists to keep the implementations apart.
+pub trait IntoSystem<Marker> {
+    type System: System;
+
+    fn into_system(self) -> Self::System;
+}
+
+/// A function that can be called with the items of its [`SystemParam`] arguments.
+pub trait SystemParamFunction<Marker>: Send + 'static {
+    type Param: SystemParam;
+    type Out;
+
+    fn run(&mut self, param: SystemParamItem<Self::Param>) -> Self::Out;
+}
+
+pub struct FunctionSystem<Marker, F: SystemParamFunction<Marker>> {
+    func: F,
+    state: <F::Param as SystemParam>::State,
+    pub(crate) meta: SystemMeta,
+    _marker: PhantomData<fn() -> Marker>,
+}
+
+impl<Marker: 'static, F: SystemParamFunction<Marker>> FunctionSystem<Marker, F> {
+    /// # Panics
+    /// If the parameters alias mutable access, e.g. `Res<T>` together with `ResMut<T>`.
+    pub(crate) fn new(func: F) -> Self {
+        let mut meta = SystemMeta {
+            name: std::any::type_name::<F>().to_string(),
+            ..Default::default()
+        };
+        let state = F::Param::init_state(&mut meta);
+
+        let conflicts = meta.access.self_conflicts();
+        assert!(
+            conflicts.is_empty(),
+            "System {} has conflicting access to {}",
+            meta.name,
+            conflicts.join(", ")
+        );
+
+        FunctionSystem {
+            func,
+            state,
+            meta,
+            _marker: PhantomData,
+        }
+    }
+
+    /// # Safety
+    /// Nothing running at the same time may conflict with the function's access.
+    pub(crate) unsafe fn call(&mut self, ctx: SystemContext) -> F::Out {
+        let this_run = ctx.resources().tick();
+        let param = F::Param::get_param(&mut self.state, &self.meta, ctx);
+        let out = self.func.run(param);
+        self.meta.last_run = this_run;
+        out
+    }
+}
+
+impl<Marker: 'static, F: SystemParamFunction<Marker, Out = ()>> System
+    for FunctionSystem<Marker, F>
+{
+    fn name(&self) -> &str {
+        &self.meta.name
+    }
+
+    fn access(&self) -> &Access {
+        &self.meta.access
+    }
+
+    unsafe fn run_unsafe(&mut self, ctx: SystemContext) {
+        self.call(ctx);
+    }
+
+    fn apply(&mut self, events: &mut EventManager) {
+        F::Param::apply(&mut self.state, events);
+    }
+}
+
+#[doc(hidden)]
+pub struct FunctionMarker;
+
+impl<Marker: 'static, F: SystemParamFunction<Marker, Out = ()>> IntoSystem<(FunctionMarker, Marker)>
+    for F
+{
+    type System = FunctionSystem<Marker, F>;
+
+    /// # Panics
+    /// If the parameters alias mutable access, e.g. `Res<T>` together with `ResMut<T>`.
+    fn into_system(self) -> Self::System {
+        FunctionSystem::new(self)
+    }
+}
+
+/// A system with exclusive access to the world, events and resources.
+pub struct ExclusiveSystem<F> {
+    func: F,
+    name: String,
+    access: Access,
+}
+
+impl<F> System for ExclusiveSystem<F>
+where
+    F: FnMut(&mut World, &mut EventManager, &mut ResourceManager) + Send + 'static,
+{
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn access(&self) -> &Access {
+        &self.access
+    }
+
+    unsafe fn run_unsafe(&mut self, ctx: SystemContext) {
+        (self.func)(ctx.world_mut(), ctx.events_mut(), ctx.resources_mut());
+    }
+
+    fn apply(&mut self, _events: &mut EventManager) {}
+}
+
+#[doc(hidden)]
+pub struct ExclusiveMarker;
+
+impl<F> IntoSystem<ExclusiveMarker> for F
+where
+    F: FnMut(&mut World, &mut EventManager, &mut ResourceManager) + Send + 'static,
+{
+    type System = ExclusiveSystem<F>;
+
+    fn into_system(self) -> Self::System {
+        ExclusiveSystem {
+            func: self,
+            name: std::any::type_name::<F>().to_string(),
+            access: Access::exclusive(),
+        }
+    }
+}
+
+macro_rules! impl_system_function {
+    ($($param:ident),*) => {
+        #[allow(non_snake_case)]
+        impl<Out, Func, $($param: SystemParam),*> SystemParamFunction<fn($($param,)*) -> Out> for Func
+        where
+            Func: Send + 'static,
+            for<'a> &'a mut Func: FnMut($($param),*) -> Out + FnMut($(SystemParamItem<$param>),*) -> Out,
+        {
+            type Param = ($($param,)*);
+            type Out = Out;
+
+            fn run(&mut self, param: SystemParamItem<($($param,)*)>) -> Out {
+                // Calling through a generic function pins down which of the two FnMut bounds
+                // is meant.
+                #[allow(clippy::too_many_arguments)]
+                fn call_inner<Out, $($param),*>(
+                    mut func: impl FnMut($($param),*) -> Out,
+                    $($param: $param),*
+                ) -> Out {
+                    func($($param),*)
+                }
+
+                let ($($param,)*) = param;
+                call_inner(self, $($param),*)
+            }
+        }
+    };
+}
+
+impl_system_function!();
+impl_system_function!(P0);
+impl_system_function!(P0, P1);
+impl_system_function!(P0, P1, P2);
+impl_system_function!(P0, P1, P2, P3);
+impl_system_function!(P0, P1, P2, P3, P4);
+impl_system_function!(P0, P1, P2, P3, P4, P5);
+impl_system_function!(P0, P1, P2, P3, P4, P5, P6);
+impl_system_function!(P0, P1, P2, P3, P4, P5, P6, P7);
+impl_system_function!(P0, P1, P2, P3, P4, P5, P6, P7, P8);
+impl_system_function!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9);
+impl_system_function!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10);
+impl_system_function!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11);
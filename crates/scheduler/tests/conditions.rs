@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod test {
+    use ecs::World;
+    use event_manager::EventManager;
+    use resource_manager::ResourceManager;
+    use scheduler::{
+        condition::{every_n_frames, in_state, not, on_event, resource_changed, resource_exists},
+        AmbiguityDetection, ExecutorKind, IntoSystemConfig, Res, ResMut, Scheduler,
+    };
+    use time::Time;
+
+    #[derive(PartialEq)]
+    enum AppState {
+        Menu,
+        Playing,
+    }
+
+    struct Hit;
+
+    #[derive(Default)]
+    struct Counts {
+        playing: u32,
+        menu: u32,
+        hits: u32,
+        every_third: u32,
+        paused: u32,
+    }
+
+    struct Paused;
+
+    #[test]
+    fn systems_only_run_when_their_conditions_hold() {
+        let mut scheduler = Scheduler::new();
+        scheduler.set_ambiguity_detection(AmbiguityDetection::Ignore);
+        scheduler.add_system(
+            (|mut counts: ResMut<Counts>| counts.playing += 1).run_if(in_state(AppState::Playing)),
+        );
+        scheduler.add_system(
+            (|mut counts: ResMut<Counts>| counts.menu += 1).run_if(in_state(AppState::Menu)),
+        );
+        scheduler.add_system(
+            (|_: &mut World, events: &mut EventManager, resources: &mut ResourceManager| {
+                events.get_event::<Hit>();
+                resources.get_mut::<Counts>().unwrap().hits += 1;
+            })
+            .run_if(on_event::<Hit>()),
+        );
+        scheduler.add_system(
+            (|mut counts: ResMut<Counts>| counts.every_third += 1)
+                .run_if(every_n_frames(3))
+                .run_if(not(resource_exists::<Paused>())),
+        );
+        scheduler.add_system(
+            (|mut counts: ResMut<Counts>| counts.paused += 1).run_if(resource_exists::<Paused>()),
+        );
+
+        let mut world = World::new();
+        let mut events = EventManager::new();
+        let mut resources = ResourceManager::new();
+        resources.add(Counts::default());
+        resources.add(AppState::Playing);
+        resources.add(Time::default());
+
+        for frame in 0..6 {
+            resources.get_mut::<Time>().unwrap().update();
+            if frame == 1 {
+                events.add(Hit);
+            }
+            if frame == 4 {
+                resources.add(AppState::Menu);
+            }
+            scheduler.update(&mut world, &mut events, &mut resources);
+        }
+
+        let counts = resources.get::<Counts>().unwrap();
+        assert_eq!(counts.playing, 4);
+        assert_eq!(counts.menu, 2);
+        assert_eq!(counts.hits, 1);
+        assert_eq!(counts.every_third, 2);
+        assert_eq!(counts.paused, 0);
+    }
+
+    struct Score(u32);
+
+    struct Level(u32);
+
+    #[derive(Default)]
+    struct Seen {
+        system: Vec<u32>,
+        condition: Vec<u32>,
+    }
+
+    #[test]
+    fn systems_see_changes_made_since_they_last_ran() {
+        let mut scheduler = Scheduler::new();
+        scheduler.set_ambiguity_detection(AmbiguityDetection::Ignore);
+        scheduler.add_system(|mut score: ResMut<Score>, mut seen: ResMut<Seen>| {
+            if score.is_changed() {
+                seen.system.push(score.0);
+            }
+            // Its own change doesn't show up the next time it runs.
+            score.0 += 0;
+        });
+        scheduler.add_system(
+            (|level: Res<Level>, mut seen: ResMut<Seen>| seen.condition.push(level.0))
+                .run_if(resource_changed::<Level>()),
+        );
+
+        let mut world = World::new();
+        let mut events = EventManager::new();
+        let mut resources = ResourceManager::new();
+        resources.add(Score(0));
+        resources.add(Level(0));
+        resources.add(Seen::default());
+
+        for frame in 0..5 {
+            if frame == 2 {
+                resources.get_mut::<Score>().unwrap().0 = 2;
+            }
+            if frame == 3 {
+                resources.add(Level(3));
+            }
+            scheduler.update(&mut world, &mut events, &mut resources);
+        }
+
+        let seen = resources.get::<Seen>().unwrap();
+        assert_eq!(seen.system, [0, 2]);
+        assert_eq!(seen.condition, [0, 3]);
+    }
+
+    struct Enabled;
+
+    #[test]
+    fn every_n_frames_counts_frames_not_checks() {
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(
+            (|time: Res<Time>, mut ran: ResMut<Vec<u64>>| ran.push(time.frame_count()))
+                .run_if(resource_exists::<Enabled>())
+                .run_if(every_n_frames(2)),
+        );
+
+        let mut resources = ResourceManager::new();
+        resources.add(Time::default());
+        resources.add(Vec::<u64>::new());
+        for frame in 1..=6 {
+            if frame == 2 {
+                resources.add(Enabled);
+            }
+            resources.get_mut::<Time>().unwrap().update();
+            scheduler.update(&mut World::new(), &mut EventManager::new(), &mut resources);
+        }
+
+        assert_eq!(*resources.get::<Vec<u64>>().unwrap(), [3, 5]);
+    }
+
+    struct Flag(bool);
+
+    #[derive(Default)]
+    struct Ran(bool);
+
+    #[test]
+    fn conditions_count_towards_access() {
+        fn set_flag(mut flag: ResMut<Flag>) {
+            flag.0 = true;
+        }
+
+        fn flagged(mut ran: ResMut<Ran>) {
+            ran.0 = true;
+        }
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(set_flag);
+        scheduler.add_system(flagged.run_if(|flag: Res<Flag>| flag.0));
+        let error = scheduler.build().unwrap_err();
+        assert_eq!(error.get_id(), "AmbiguousSystemOrder");
+        assert!(error.get_msg().contains("Flag"));
+
+        for executor in [ExecutorKind::SingleThreaded, ExecutorKind::MultiThreaded] {
+            let mut scheduler = Scheduler::new();
+            scheduler.set_executor(executor);
+            scheduler.set_ambiguity_detection(AmbiguityDetection::Ignore);
+            scheduler.add_system(set_flag);
+            scheduler.add_system(flagged.run_if(|flag: Res<Flag>| flag.0));
+
+            let mut resources = ResourceManager::new();
+            resources.add(Flag(false));
+            resources.add(Ran::default());
+            scheduler.update(&mut World::new(), &mut EventManager::new(), &mut resources);
+            assert!(resources.get::<Ran>().unwrap().0, "{executor:?}");
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "every_n_frames needs a frame count above 0")]
+    fn every_zero_frames_panics() {
+        every_n_frames(0);
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use ecs::World;
+    use event_manager::EventManager;
+    use resource_manager::ResourceManager;
+    use scheduler::{FixedTime, ResMut, Scheduler, Stage};
+    use time::Time;
+
+    #[derive(Default)]
+    struct Steps(u32);
+
+    #[test]
+    fn accumulated_time_is_spent_in_whole_steps() {
+        let mut fixed = FixedTime::new(Duration::from_millis(10));
+        fixed.accumulate(Duration::from_millis(25));
+
+        assert!(fixed.expend());
+        assert!(fixed.expend());
+        assert!(!fixed.expend());
+        assert_eq!(fixed.steps(), 2);
+        assert!((fixed.alpha() - 0.5).abs() < 1e-3);
+
+        fixed.accumulate(Duration::from_secs(10));
+        assert_eq!(fixed.accumulated(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn steps_longer_than_the_cap_still_run() {
+        let mut fixed = FixedTime::from_hz(2.0);
+        fixed.accumulate(Duration::from_secs(10));
+        assert!(fixed.expend());
+        assert!(!fixed.expend());
+    }
+
+    #[test]
+    #[should_panic(expected = "has to be a positive number")]
+    fn zero_hz_is_rejected() {
+        FixedTime::from_hz(0.0);
+    }
+
+    #[test]
+    fn fixed_stage_runs_once_per_step() {
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system_to_stage(Stage::FixedUpdate, |mut steps: ResMut<Steps>| steps.0 += 1);
+
+        let mut world = World::new();
+        let mut events = EventManager::new();
+        let mut resources = ResourceManager::new();
+        resources.add(Steps::default());
+
+        resources.add(FixedTime::from_hz(100.0));
+        resources.add(Time::default());
+
+        resources
+            .get_mut_unchecked::<Time>()
+            .advance_by(Duration::from_millis(35));
+        scheduler.update(&mut world, &mut events, &mut resources);
+        assert_eq!(resources.get::<Steps>().unwrap().0, 3);
+        assert!((resources.get::<FixedTime>().unwrap().alpha() - 0.5).abs() < 1e-3);
+
+        resources.get_mut_unchecked::<Time>().pause();
+        resources
+            .get_mut_unchecked::<Time>()
+            .advance_by(Duration::from_millis(35));
+        scheduler.update(&mut world, &mut events, &mut resources);
+        assert_eq!(resources.get::<Steps>().unwrap().0, 3);
+    }
+
+    #[test]
+    fn fixed_time_is_added_if_missing() {
+        let mut scheduler = Scheduler::new();
+        let mut resources = ResourceManager::new();
+        scheduler.update(&mut World::new(), &mut EventManager::new(), &mut resources);
+
+        assert_eq!(
+            resources.get::<FixedTime>().unwrap().step(),
+            FixedTime::default().step()
+        );
+    }
+}
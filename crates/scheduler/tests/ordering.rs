@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod test {
+    use ecs::World;
+    use event_manager::EventManager;
+    use resource_manager::ResourceManager;
+    use scheduler::{AmbiguityDetection, IntoSystemConfig, Res, ResMut, Scheduler};
+
+    #[derive(Default)]
+    struct Log(Vec<&'static str>);
+
+    fn input(mut log: ResMut<Log>) {
+        log.0.push("input");
+    }
+
+    fn physics(mut log: ResMut<Log>) {
+        log.0.push("physics");
+    }
+
+    fn transform(mut log: ResMut<Log>) {
+        log.0.push("transform");
+    }
+
+    fn run(scheduler: &mut Scheduler) -> Vec<&'static str> {
+        let mut resources = ResourceManager::new();
+        resources.add(Log::default());
+        scheduler.update(&mut World::new(), &mut EventManager::new(), &mut resources);
+        resources.remove::<Log>().unwrap().0
+    }
+
+    #[test]
+    fn constraints_override_insertion_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(transform.label("transform"));
+        scheduler.add_system(physics.label("physics").before("transform").after("input"));
+        scheduler.add_system(input.label("input"));
+
+        assert_eq!(run(&mut scheduler), vec!["input", "physics", "transform"]);
+    }
+
+    #[test]
+    fn unconstrained_systems_keep_insertion_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.set_ambiguity_detection(AmbiguityDetection::Warn);
+        scheduler.add_system(physics);
+        scheduler.add_system(input.before("transform"));
+        scheduler.add_system(transform.label("transform"));
+
+        assert_eq!(run(&mut scheduler), vec!["physics", "input", "transform"]);
+    }
+
+    #[test]
+    fn cycles_are_errors() {
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(input.label("input").after("physics"));
+        scheduler.add_system(physics.label("physics").after("input"));
+        scheduler.add_system(transform);
+
+        let error = scheduler.build().unwrap_err();
+        assert_eq!(error.get_id(), "SystemOrderCycle");
+        assert!(error.get_msg().contains("input"));
+        assert!(error.get_msg().contains("physics"));
+        assert!(!error.get_msg().contains("transform"));
+    }
+
+    #[test]
+    fn ambiguities_are_denied_by_default() {
+        fn reader(_: Res<Log>) {}
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(reader);
+        scheduler.add_system(input.label("input"));
+        scheduler.add_system(physics.after("input"));
+
+        let error = scheduler.build().unwrap_err();
+        assert_eq!(error.get_id(), "AmbiguousSystemOrder");
+        assert!(error.get_msg().contains("reader"));
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(reader.before("input"));
+        scheduler.add_system(input.label("input"));
+        scheduler.add_system(physics.after("input"));
+        assert!(scheduler.build().is_ok());
+
+        for opt_out in [AmbiguityDetection::Warn, AmbiguityDetection::Ignore] {
+            let mut scheduler = Scheduler::new();
+            scheduler.set_ambiguity_detection(opt_out);
+            scheduler.add_system(reader);
+            scheduler.add_system(input);
+            assert!(scheduler.build().is_ok());
+        }
+    }
+}
@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod test {
+    use std::{
+        collections::HashSet,
+        num::NonZeroUsize,
+        rc::Rc,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+        thread::{self, ThreadId},
+        time::{Duration, Instant},
+    };
+
+    use ecs::World;
+    use event_manager::EventManager;
+    use resource_manager::ResourceManager;
+    use scheduler::{AmbiguityDetection, ExecutorKind, NonSend, Query, Res, ResMut, Scheduler};
+
+    struct Position(i32);
+    struct Velocity;
+
+    #[derive(Default)]
+    struct Met(AtomicUsize);
+
+    /// Waits until `count` systems got here at the same time, which can only happen when they
+    /// run in parallel.
+    fn rendezvous(met: &Met, count: usize) -> bool {
+        met.0.fetch_add(1, Ordering::SeqCst);
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(2) {
+            if met.0.load(Ordering::SeqCst) >= count {
+                return true;
+            }
+            std::thread::yield_now();
+        }
+        false
+    }
+
+    fn setup() -> (World, EventManager, ResourceManager) {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.insert(entity, Position(0));
+        world.insert(entity, Velocity);
+
+        let mut resources = ResourceManager::new();
+        resources.add(Met::default());
+        resources.add(Vec::<bool>::new());
+        (world, EventManager::new(), resources)
+    }
+
+    #[test]
+    fn disjoint_systems_run_in_parallel() {
+        let results = Arc::new(Mutex::new(Vec::new()));
+        let mut scheduler = Scheduler::new();
+        scheduler.set_worker_threads(NonZeroUsize::new(2).unwrap());
+        for _ in 0..2 {
+            let results = results.clone();
+            scheduler.add_system(move |met: Res<Met>, _: Query<&Position>| {
+                let met = rendezvous(&met, 2);
+                results.lock().unwrap().push(met);
+            });
+        }
+        let moved = results.clone();
+        scheduler.add_system(move |met: Res<Met>, _: Query<&mut Velocity>| {
+            let met = rendezvous(&met, 3);
+            moved.lock().unwrap().push(met);
+        });
+
+        let (mut world, mut events, mut resources) = setup();
+        scheduler.update(&mut world, &mut events, &mut resources);
+
+        assert_eq!(*results.lock().unwrap(), vec![true; 3]);
+    }
+
+    #[test]
+    fn conflicting_systems_run_one_after_another() {
+        let mut scheduler = Scheduler::new();
+        scheduler.set_ambiguity_detection(AmbiguityDetection::Ignore);
+        scheduler.add_system(
+            |mut position: Query<&mut Position>, mut log: ResMut<Vec<bool>>| {
+                for position in position.iter_mut() {
+                    position.0 += 1;
+                }
+                log.push(true);
+            },
+        );
+        scheduler.add_system(|position: Query<&Position>| {
+            assert_eq!(position.iter().next().unwrap().0, 1);
+        });
+
+        let (mut world, mut events, mut resources) = setup();
+        scheduler.update(&mut world, &mut events, &mut resources);
+        assert_eq!(resources.get::<Vec<bool>>().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn single_threaded_executor_runs_in_order() {
+        let mut scheduler = Scheduler::new();
+        scheduler.set_executor(ExecutorKind::SingleThreaded);
+        scheduler.add_system(|met: Res<Met>, mut log: ResMut<Vec<bool>>| {
+            met.0.fetch_add(1, Ordering::SeqCst);
+            log.push(true);
+        });
+        scheduler.add_system(|met: Res<Met>| {
+            assert_eq!(met.0.load(Ordering::SeqCst), 1);
+        });
+
+        let (mut world, mut events, mut resources) = setup();
+        scheduler.update(&mut world, &mut events, &mut resources);
+    }
+
+    #[test]
+    fn non_send_systems_run_on_the_calling_thread() {
+        let threads = Arc::new(Mutex::new(Vec::<ThreadId>::new()));
+        let mut scheduler = Scheduler::new();
+        for _ in 0..2 {
+            let threads = threads.clone();
+            scheduler.add_system(move |_: NonSend<Rc<()>>, _: Res<Met>| {
+                threads.lock().unwrap().push(thread::current().id());
+            });
+        }
+        scheduler.add_system(|_: Res<Met>, _: Query<&Position>| {});
+
+        let (mut world, mut events, mut resources) = setup();
+        resources.add_non_send(Rc::new(()));
+        scheduler.update(&mut world, &mut events, &mut resources);
+
+        assert_eq!(*threads.lock().unwrap(), vec![thread::current().id(); 2]);
+    }
+
+    #[test]
+    fn worker_threads_are_kept_between_frames() {
+        let threads = Arc::new(Mutex::new(HashSet::new()));
+        let mut scheduler = Scheduler::new();
+        scheduler.set_worker_threads(NonZeroUsize::new(2).unwrap());
+        for _ in 0..3 {
+            let threads = threads.clone();
+            scheduler.add_system(move |_: Res<Met>| {
+                threads.lock().unwrap().insert(thread::current().id());
+            });
+        }
+
+        let (mut world, mut events, mut resources) = setup();
+        for _ in 0..10 {
+            scheduler.update(&mut world, &mut events, &mut resources);
+        }
+
+        // The calling thread and the two workers, rather than new threads every frame.
+        assert!(threads.lock().unwrap().len() <= 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "worker system panicked")]
+    fn panics_on_workers_reach_the_caller() {
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(|_: Res<Met>| {});
+        scheduler.add_system(|_: Res<Met>| panic!("worker system panicked"));
+
+        let (mut world, mut events, mut resources) = setup();
+        scheduler.update(&mut world, &mut events, &mut resources);
+    }
+}
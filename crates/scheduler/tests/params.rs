@@ -0,0 +1,152 @@
+#[cfg(test)]
+mod test {
+    use ecs::World;
+    use event_manager::EventManager;
+    use resource_manager::ResourceManager;
+    use scheduler::{
+        AmbiguityDetection, EventReader, EventWriter, Query, Res, ResMut, Scheduler, With,
+    };
+
+    struct Position(i32);
+    struct Velocity(i32);
+    struct Player;
+
+    struct Time(i32);
+
+    #[derive(Default)]
+    struct Score(u32);
+
+    struct Hit(i32);
+
+    fn movement(
+        time: Res<Time>,
+        mut query: Query<(&mut Position, &Velocity)>,
+        mut hits: EventWriter<Hit>,
+    ) {
+        for (position, velocity) in query.iter_mut() {
+            position.0 += velocity.0 * time.0;
+            if position.0 > 10 {
+                hits.send(Hit(position.0));
+            }
+        }
+    }
+
+    fn scoring(mut score: ResMut<Score>, players: Query<&Position, With<Player>>) {
+        score.0 += players.iter().count() as u32;
+    }
+
+    fn optional(missing: Option<Res<String>>, mut score: Option<ResMut<Score>>) {
+        assert!(missing.is_none());
+        score.as_mut().unwrap().0 += 100;
+    }
+
+    fn setup() -> (World, EventManager, ResourceManager) {
+        let mut world = World::new();
+        for i in 0..3 {
+            let entity = world.spawn();
+            world.insert(entity, Position(i * 5));
+            world.insert(entity, Velocity(1));
+            if i == 0 {
+                world.insert(entity, Player);
+            }
+        }
+
+        let mut resources = ResourceManager::new();
+        resources.add(Time(2));
+        resources.add(Score::default());
+
+        (world, EventManager::new(), resources)
+    }
+
+    #[test]
+    fn function_systems_get_their_params() {
+        let (mut world, mut events, mut resources) = setup();
+
+        let mut scheduler = Scheduler::new();
+        scheduler.set_ambiguity_detection(AmbiguityDetection::Ignore);
+        scheduler.add_system(movement);
+        scheduler.add_system(scoring);
+        scheduler.add_system(optional);
+        scheduler.update(&mut world, &mut events, &mut resources);
+
+        let mut positions: Vec<_> = world.iter::<Position>().map(|(_, p)| p.0).collect();
+        positions.sort();
+        assert_eq!(positions, vec![2, 7, 12]);
+        assert_eq!(resources.get::<Score>().unwrap().0, 101);
+        assert_eq!(events.get_event::<Hit>().map(|hit| hit.0), Some(12));
+    }
+
+    #[test]
+    fn every_reader_sees_every_event() {
+        let (mut world, mut events, mut resources) = setup();
+
+        let mut scheduler = Scheduler::new();
+        scheduler.set_ambiguity_detection(AmbiguityDetection::Ignore);
+        for _ in 0..2 {
+            scheduler.add_system(|mut hits: EventReader<Hit>, mut score: ResMut<Score>| {
+                score.0 += hits.read().map(|hit| hit.0 as u32).sum::<u32>();
+            });
+        }
+
+        events.add(Hit(1));
+        scheduler.update(&mut world, &mut events, &mut resources);
+        events.update();
+        events.add(Hit(2));
+        scheduler.update(&mut world, &mut events, &mut resources);
+        events.update();
+        scheduler.update(&mut world, &mut events, &mut resources);
+
+        assert_eq!(resources.get::<Score>().unwrap().0, 6);
+    }
+
+    #[test]
+    fn closures_with_params() {
+        let (mut world, mut events, mut resources) = setup();
+
+        let mut runs = 0;
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(move |mut score: ResMut<Score>| {
+            runs += 1;
+            score.0 = runs;
+        });
+        scheduler.update(&mut world, &mut events, &mut resources);
+        scheduler.update(&mut world, &mut events, &mut resources);
+
+        assert_eq!(resources.get::<Score>().unwrap().0, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicting access")]
+    fn conflicting_params_are_rejected() {
+        fn conflicting(_: Res<Score>, _: ResMut<Score>) {}
+
+        Scheduler::new().add_system(conflicting);
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicting access")]
+    fn conflicting_queries_are_rejected() {
+        fn conflicting(_: Query<&mut Position>, _: Query<(&Position, &Velocity)>) {}
+
+        Scheduler::new().add_system(conflicting);
+    }
+
+    #[test]
+    #[should_panic(expected = "conflicting access")]
+    fn filters_conflict_with_mutable_queries() {
+        fn conflicting(_: Query<&mut Player>, _: Query<&Position, With<Player>>) {}
+
+        Scheduler::new().add_system(conflicting);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not exist")]
+    fn missing_resources_name_the_type() {
+        fn needs_string(_: Res<String>) {}
+
+        let (mut world, mut events, mut resources) = setup();
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(needs_string);
+        scheduler.update(&mut world, &mut events, &mut resources);
+    }
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod test {
+    use ecs::World;
+    use event_manager::EventManager;
+    use resource_manager::ResourceManager;
+    use scheduler::{ResMut, Scheduler, Stage};
+
+    struct Position(i32);
+
+    #[test]
+    fn systems_can_query_the_world() {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.insert(entity, Position(0));
+
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system(
+            |world: &mut World, _: &mut EventManager, _: &mut ResourceManager| {
+                for position in world.query::<&mut Position>().iter_mut() {
+                    position.0 += 1;
+                }
+            },
+        );
+
+        let mut events = EventManager::new();
+        let mut resources = ResourceManager::new();
+        scheduler.update(&mut world, &mut events, &mut resources);
+        scheduler.update(&mut world, &mut events, &mut resources);
+
+        assert_eq!(world.get::<Position>(entity).unwrap().0, 2);
+    }
+
+    #[derive(Default)]
+    struct Log(Vec<&'static str>);
+
+    #[test]
+    fn stages_run_in_order_and_startup_runs_once() {
+        let mut scheduler = Scheduler::new();
+        scheduler.add_system_to_stage(Stage::Last, |mut log: ResMut<Log>| log.0.push("last"));
+        scheduler.add_system_to_stage(Stage::Render, |mut log: ResMut<Log>| log.0.push("render"));
+        scheduler.add_system(|mut log: ResMut<Log>| log.0.push("update"));
+        scheduler.add_system_to_stage(Stage::PreUpdate, |mut log: ResMut<Log>| log.0.push("pre"));
+        scheduler.add_startup_system(|mut log: ResMut<Log>| log.0.push("startup"));
+
+        let mut world = World::new();
+        let mut events = EventManager::new();
+        let mut resources = ResourceManager::new();
+        resources.add(Log::default());
+        scheduler.update(&mut world, &mut events, &mut resources);
+        scheduler.update(&mut world, &mut events, &mut resources);
+
+        assert_eq!(
+            resources.get::<Log>().unwrap().0,
+            vec!["startup", "pre", "update", "render", "last", "pre", "update", "render", "last"]
+        );
+    }
+}
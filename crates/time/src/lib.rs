@@ -0,0 +1,132 @@
+use std::time::{Duration, Instant};
+
+pub mod prelude {
+    pub use super::Time;
+}
+
+/// Frame timing, advanced once per frame so every system sees the same numbers.
+///
+/// There are two clocks. The real clock follows the wall clock. The virtual clock is the one
+/// gameplay should use: it can be paused and sped up or slowed down, and [`Time::delta`] and
+/// [`Time::elapsed`] report it.
+#[derive(Clone, Debug)]
+pub struct Time {
+    startup: Instant,
+    last_update: Option<Instant>,
+    frame_count: u64,
+
+    real_delta: Duration,
+    real_elapsed: Duration,
+
+    delta: Duration,
+    elapsed: Duration,
+    relative_speed: f64,
+    paused: bool,
+}
+
+impl Default for Time {
+    fn default() -> Self {
+        Self::new(Instant::now())
+    }
+}
+
+impl Time {
+    pub fn new(startup: Instant) -> Self {
+        Self {
+            startup,
+            last_update: None,
+            frame_count: 0,
+            real_delta: Duration::ZERO,
+            real_elapsed: Duration::ZERO,
+            delta: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            relative_speed: 1.0,
+            paused: false,
+        }
+    }
+
+    /// Starts a new frame, measuring its delta from the last one.
+    pub fn update(&mut self) {
+        self.update_with_instant(Instant::now());
+    }
+
+    /// Starts a new frame at `now`. The first frame measures its delta from startup.
+    pub fn update_with_instant(&mut self, now: Instant) {
+        let last_update = self.last_update.unwrap_or(self.startup);
+        self.last_update = Some(now);
+        self.advance_by(now.saturating_duration_since(last_update));
+    }
+
+    /// Starts a new frame that took `real_delta`, without looking at the wall clock.
+    pub fn advance_by(&mut self, real_delta: Duration) {
+        self.frame_count += 1;
+        self.real_delta = real_delta;
+        self.real_elapsed += real_delta;
+
+        self.delta = if self.paused {
+            Duration::ZERO
+        } else {
+            real_delta.mul_f64(self.relative_speed)
+        };
+        self.elapsed += self.delta;
+    }
+
+    pub fn startup(&self) -> Instant {
+        self.startup
+    }
+
+    /// How many frames have started, including the current one.
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    pub fn delta(&self) -> Duration {
+        self.delta
+    }
+
+    pub fn delta_secs(&self) -> f32 {
+        self.delta.as_secs_f32()
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn elapsed_secs(&self) -> f32 {
+        self.elapsed.as_secs_f32()
+    }
+
+    pub fn real_delta(&self) -> Duration {
+        self.real_delta
+    }
+
+    pub fn real_elapsed(&self) -> Duration {
+        self.real_elapsed
+    }
+
+    pub fn relative_speed(&self) -> f64 {
+        self.relative_speed
+    }
+
+    /// Scales how fast the virtual clock runs compared to the real one, from the next frame on.
+    pub fn set_relative_speed(&mut self, relative_speed: f64) {
+        assert!(
+            relative_speed.is_finite() && relative_speed >= 0.0,
+            "The relative speed of time has to be a non-negative number, got {relative_speed}"
+        );
+        self.relative_speed = relative_speed;
+    }
+
+    /// Stops the virtual clock from the next frame on. The real clock keeps running.
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn unpause(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use time::Time;
+
+    #[test]
+    fn frames_advance_both_clocks() {
+        let startup = Instant::now();
+        let mut time = Time::new(startup);
+
+        time.update_with_instant(startup + Duration::from_millis(16));
+        time.update_with_instant(startup + Duration::from_millis(40));
+
+        assert_eq!(time.frame_count(), 2);
+        assert_eq!(time.delta(), Duration::from_millis(24));
+        assert_eq!(time.elapsed(), Duration::from_millis(40));
+        assert_eq!(time.real_delta(), Duration::from_millis(24));
+        assert_eq!(time.real_elapsed(), Duration::from_millis(40));
+    }
+
+    #[test]
+    fn virtual_clock_can_be_scaled_and_paused() {
+        let mut time = Time::default();
+
+        time.set_relative_speed(2.0);
+        time.advance_by(Duration::from_millis(10));
+        assert_eq!(time.delta(), Duration::from_millis(20));
+
+        time.pause();
+        time.advance_by(Duration::from_millis(10));
+        assert_eq!(time.delta(), Duration::ZERO);
+        assert_eq!(time.elapsed(), Duration::from_millis(20));
+        assert_eq!(time.real_elapsed(), Duration::from_millis(20));
+
+        time.unpause();
+        time.set_relative_speed(0.5);
+        time.advance_by(Duration::from_millis(10));
+        assert_eq!(time.delta(), Duration::from_millis(5));
+        assert_eq!(time.frame_count(), 3);
+    }
+}
@@ -1 +1,34 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
 
+/// Orders the nodes `0..successors.len()` so every node comes before its successors, keeping
+/// lower indices first wherever the edges allow it.
+///
+/// Fails with the nodes that are part of, or come after, a cycle.
+pub fn topological_sort(successors: &[Vec<usize>]) -> Result<Vec<usize>, Vec<usize>> {
+    let count = successors.len();
+    let mut in_degree = vec![0; count];
+    for &next in successors.iter().flatten() {
+        in_degree[next] += 1;
+    }
+
+    let mut ready: BinaryHeap<_> = (0..count)
+        .filter(|index| in_degree[*index] == 0)
+        .map(Reverse)
+        .collect();
+    let mut order = Vec::with_capacity(count);
+    while let Some(Reverse(index)) = ready.pop() {
+        order.push(index);
+        for &next in &successors[index] {
+            in_degree[next] -= 1;
+            if in_degree[next] == 0 {
+                ready.push(Reverse(next));
+            }
+        }
+    }
+
+    if order.len() == count {
+        Ok(order)
+    } else {
+        Err((0..count).filter(|index| in_degree[*index] > 0).collect())
+    }
+}
@@ -1,5 +1,6 @@
-use app_base::{App, Plugin};
+use app_base::{App, AppExit, Plugin};
 use logger::trace;
+use winit::platform::run_return::EventLoopExtRunReturn;
 
 pub struct WindowPlugin;
 
@@ -16,38 +17,52 @@ impl Plugin for WindowPlugin {
             .unwrap();
 
         app.get_resource_manager_mut()
-            .add(WindowResource { window, event_loop });
+            .add_non_send(WindowResource { window, event_loop });
     }
 }
 
+/// Main-thread-only, get it through the resource manager's non-send methods.
 pub struct WindowResource {
     pub window: winit::window::Window,
     event_loop: winit::event_loop::EventLoop<()>,
 }
 
-fn run_window(mut app: App) {
-    let WindowResource { window, event_loop } = app
+fn run_window(mut app: App) -> AppExit {
+    let WindowResource {
+        window,
+        mut event_loop,
+    } = app
         .get_resource_manager_mut()
-        .remove_unchecked::<WindowResource>();
+        .try_remove_non_send::<WindowResource>()
+        .unwrap_or_else(|err| panic!("{}", err.get_msg()));
 
-    event_loop.run(move |event, _, control_flow| match event {
-        winit::event::Event::WindowEvent { event, .. } => match event {
-            winit::event::WindowEvent::CloseRequested => {
-                control_flow.set_exit();
-                app.get_event_manager_mut().add(CloseRequestedEvent);
-            }
-            _ => (),
-        },
+    let mut exit = AppExit::default();
+    event_loop.run_return(|event, _, control_flow| match event {
+        // Systems get one more frame to see the exit before the loop stops.
+        winit::event::Event::WindowEvent {
+            event: winit::event::WindowEvent::CloseRequested,
+            ..
+        } => {
+            app.get_event_manager_mut().add(AppExit::default());
+        }
         winit::event::Event::RedrawRequested(_) => {
             app.get_event_manager_mut().add(RedrawRequestedEvent);
         }
         winit::event::Event::MainEventsCleared => {
             window.request_redraw();
             app.update();
+
+            if let Some(requested) = app.exit_requested() {
+                exit = requested;
+                control_flow.set_exit_with_code(exit.code());
+            }
         }
         _ => (),
     });
+
+    // The window is still open, so plugins can tear down what they made with it.
+    app.cleanup();
+    exit
 }
 
-struct CloseRequestedEvent;
 pub struct RedrawRequestedEvent;
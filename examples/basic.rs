@@ -1,8 +1,5 @@
-use spyder::{App, RasterizationRendererPlugin, WindowPlugin};
+use spyder::{App, AppExit, DefaultPlugins};
 
-fn main() {
-    App::new()
-        .add_plugin(WindowPlugin)
-        .add_plugin(RasterizationRendererPlugin)
-        .run();
+fn main() -> AppExit {
+    App::new().add_plugins(DefaultPlugins).run()
 }
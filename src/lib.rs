@@ -1,3 +1,14 @@
-pub use app_base::{App, Plugin};
+pub use app_base::{App, AppExit, Plugin, PluginGroup, PluginGroupBuilder};
 pub use renderer_rasterization::RasterizationRendererPlugin;
 pub use window::WindowPlugin;
+
+/// The plugins a windowed app needs: [`WindowPlugin`] and [`RasterizationRendererPlugin`].
+pub struct DefaultPlugins;
+
+impl PluginGroup for DefaultPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(WindowPlugin)
+            .add(RasterizationRendererPlugin)
+    }
+}